@@ -0,0 +1,100 @@
+//! Parsing and formatting UUIDs in the hex formats, compared with the implementation
+//! that preceded the SIMD fast paths. Run with `cargo bench --bench hex`.
+
+use std::fmt::Write;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use phf::phf_map;
+use uuidland::Uuid;
+
+/// The previous implementation: a map lookup per character, shifting a `u128`
+mod baseline {
+    use super::*;
+
+    static HEX_TO_INT_TBL: phf::Map<char, u8> = phf_map! {
+        '0' => 0, '1' => 1, '2' => 2, '3' => 3, '4' => 4,
+        '5' => 5, '6' => 6, '7' => 7, '8' => 8, '9' => 9,
+        'a' => 10, 'b' => 11, 'c' => 12, 'd' => 13, 'e' => 14, 'f' => 15,
+        'A' => 10, 'B' => 11, 'C' => 12, 'D' => 13, 'E' => 14, 'F' => 15,
+    };
+
+    pub fn parse(value: &str) -> Option<u128> {
+        let (digits, dashed) = match value.len() {
+            36 => (value, true),
+            32 => (value, false),
+            34 if value.starts_with("0x") || value.starts_with("0X") => (&value[2..], false),
+            _ => return None,
+        };
+
+        let mut intval = 0u128;
+
+        for (i, s) in digits.chars().enumerate() {
+            let as_int: u128 = match (dashed, i, s) {
+                (true, 8 | 13 | 18 | 23, '-') => continue,
+                (true, 8 | 13 | 18 | 23, _) => return None,
+                (_, _, s) => *HEX_TO_INT_TBL.get(&s)? as u128,
+            };
+
+            intval = intval << 4 | as_int;
+        }
+
+        Some(intval)
+    }
+
+    pub fn format_hyphenated(value: u128) -> String {
+        let octets = value.to_be_bytes();
+        let mut output = String::with_capacity(36);
+
+        for (i, oct) in octets.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                output.push('-');
+            }
+
+            write!(output, "{:02x}", oct).unwrap();
+        }
+
+        output
+    }
+
+    pub fn format_simple(value: u128) -> String {
+        let mut output = String::with_capacity(32);
+
+        for oct in value.to_be_bytes() {
+            output.push_str(&format!("{:02x}", oct));
+        }
+
+        output
+    }
+}
+
+const HYPHENATED: &str = "ffb82219-2be8-4961-8c83-2163e1b4b966";
+const SIMPLE: &str = "FFB822192BE849618C832163E1B4B966";
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    group.bench_function("hyphenated", |b| b.iter(|| Uuid::parse(black_box(HYPHENATED))));
+    group.bench_function("hyphenated/baseline", |b| b.iter(|| baseline::parse(black_box(HYPHENATED))));
+    group.bench_function("simple", |b| b.iter(|| Uuid::parse(black_box(SIMPLE))));
+    group.bench_function("simple/baseline", |b| b.iter(|| baseline::parse(black_box(SIMPLE))));
+
+    group.finish();
+}
+
+fn format(c: &mut Criterion) {
+    let uuid = Uuid::parse(HYPHENATED).unwrap();
+    let mut group = c.benchmark_group("format");
+
+    group.bench_function("hyphenated", |b| b.iter(|| black_box(&uuid).to_string_hex()));
+    group.bench_function("hyphenated/baseline", |b| {
+        b.iter(|| baseline::format_hyphenated(black_box(uuid.value())))
+    });
+    group.bench_function("simple", |b| b.iter(|| black_box(&uuid).to_string_hex_joined()));
+    group.bench_function("simple/baseline", |b| b.iter(|| baseline::format_simple(black_box(uuid.value()))));
+
+    group.finish();
+}
+
+criterion_group!(benches, parse, format);
+criterion_main!(benches);
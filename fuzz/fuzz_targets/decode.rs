@@ -0,0 +1,32 @@
+//! Decodes arbitrary strings with every encoding. Decoded UUIDs must encode back to
+//! an equivalent string.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uuidland::encoding::ShortCodec;
+use uuidland::typeid::TypeId;
+use uuidland::Uuid;
+
+fuzz_target!(|value: &str| {
+    if let Ok(uuid) = Uuid::from_base32(value) {
+        assert!(uuid.to_base32().eq_ignore_ascii_case(value));
+    }
+
+    if let Ok(uuid) = Uuid::from_base58(value) {
+        assert_eq!(uuid.to_base58(), value);
+    }
+
+    if let Ok(uuid) = Uuid::from_base64url(value) {
+        assert_eq!(uuid.to_base64url(), value);
+    }
+
+    let codec = ShortCodec::default();
+    if let Ok(uuid) = codec.decode(value) {
+        assert_eq!(codec.decode(&codec.encode(&uuid)), Ok(uuid));
+    }
+
+    if let Ok(typeid) = value.parse::<TypeId>() {
+        assert_eq!(typeid.to_string(), value);
+    }
+});
@@ -0,0 +1,19 @@
+//! Parses arbitrary strings. Accepted input must be one of the formats produced by
+//! the formatting code, and must parse the same way at compile time.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uuidland::Uuid;
+
+fuzz_target!(|value: &str| {
+    let Ok(uuid) = Uuid::parse(value) else {
+        return;
+    };
+
+    let lower = value.to_ascii_lowercase();
+    let simple = uuid.to_string_hex_joined();
+
+    assert!(lower == uuid.to_string_hex() || lower == simple || lower == format!("0x{simple}"));
+    assert_eq!(Uuid::parse_const(value), uuid);
+});
@@ -0,0 +1,23 @@
+//! Formats arbitrary UUIDs in every format and parses them back.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uuidland::encoding::ShortCodec;
+use uuidland::Uuid;
+
+fuzz_target!(|uuid: Uuid| {
+    let simple = uuid.to_string_hex_joined();
+
+    assert_eq!(Uuid::parse(uuid.to_string_hex()), Ok(uuid.clone()));
+    assert_eq!(Uuid::parse(&simple), Ok(uuid.clone()));
+    assert_eq!(Uuid::parse(format!("0x{simple}")), Ok(uuid.clone()));
+
+    assert_eq!(Uuid::from_base32(&uuid.to_base32()), Ok(uuid.clone()));
+    assert_eq!(Uuid::from_ulid_str(&uuid.to_ulid_string()), Ok(uuid.clone()));
+    assert_eq!(Uuid::from_base58(&uuid.to_base58()), Ok(uuid.clone()));
+    assert_eq!(Uuid::from_base64url(&uuid.to_base64url()), Ok(uuid.clone()));
+
+    let codec = ShortCodec::default();
+    assert_eq!(codec.decode(&codec.encode(&uuid)), Ok(uuid));
+});
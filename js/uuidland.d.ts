@@ -0,0 +1,148 @@
+/* tslint:disable */
+/* eslint-disable */
+
+/**
+ * The time information returned by `UuidDetails::unix_time()` method
+ *
+ * UUIDs contain a timestamp with resolution upto nanoseconds (read below), which sometimes doesn't fit
+ * in a single 64-bit integer. This struct contains the timestamp in a destructed way in multiple
+ * integers, namely `seconds`, `microseconds` and `nanoseconds`. `seconds` stores the number of
+ * seconds in the timestamp. `microseconds` stores the additional microseconds, after one has taken
+ * `seconds` into the account. Similarly, `nanoseconds` contains the additional nanoseconds,
+ * after one taken into account `microseconds`.
+ *
+ * As an example, the full timestamp as a single number in nanoseconds is given by
+ * `seconds * 1_000_000_000 + microseconds * 1000 + nanoseconds` (see [`TimeSpec::as_nanos`])
+ *
+ * `seconds` is negative for timestamps before 00:00:00.00, 01 January 1970, which
+ * UUIDs can represent back to 15 October 1582. `microseconds` and `nanoseconds` are
+ * never negative, so 100 nanoseconds before the Unix epoch is represented as
+ * `seconds = -1`, `microseconds = 999999` and `nanoseconds = 900`.
+ *
+ * UUIDs store time as count of 100-nanoseconds intervals. So the maximun resolution available is
+ * 100-nanoseconds. As a result, `nanoseconds` will always be a multiple of 100
+ *
+ * A `TimeSpec` can be converted to [`core::time::Duration`] (since the Unix epoch),
+ * `std::time::SystemTime`, and with the `chrono` and `time` features to
+ * `chrono::DateTime<Utc>` and `time::OffsetDateTime`, using `TryFrom`.
+ */
+export class TimeSpec {
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * Microseconds of the timestamp
+     */
+    microseconds: number;
+    /**
+     * nanoseconds of the timestamp.
+     */
+    nanoseconds: number;
+    /**
+     * Seconds of the timestamp
+     */
+    seconds: bigint;
+}
+
+export class UuidDetails {
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * The 14-bit clock sequence of the UUID
+     */
+    clock_seq: number;
+    /**
+     * The 48-bit node field of the UUID
+     */
+    node: bigint;
+    /**
+     * The 60-bit time field of the UUID, read with the layout of V1 UUIDs. For V1
+     * UUIDs this is the number of 100-nanosecond intervals since 00:00:00.00,
+     * 15 October 1582. Other versions store time differently or not at all, so
+     * prefer [`UuidDetails::timestamp`] which takes the version into account
+     */
+    time: bigint;
+    /**
+     * The 8-bit variant field of the UUID.
+     *
+     * UUID variant is encoded in a variable number of bits. For this reason this contains
+     * the full octet containing the variant, but with all the clock sequence bits set to 0.
+     * This is 0 for the Nil and Max UUIDs, which have no variant
+     */
+    variant: number;
+    /**
+     * The 4-bit version field of the UUID. This is 0 for the Nil and Max UUIDs, which
+     * have no version (see [`UuidDetails::special`])
+     */
+    version: number;
+}
+
+/**
+ * The individual fields of a UUID as per RFC 4122. Each field is stored in
+ * big-endian order
+ */
+export class UuidFields {
+    private constructor();
+    free(): void;
+    [Symbol.dispose](): void;
+    clk_seq_hi_res: number;
+    clk_seq_low: number;
+    node: bigint;
+    time_hi_and_version: number;
+    time_low: number;
+    time_mid: number;
+}
+
+/**
+ * Builds a UUID from 16 octets, most significant first
+ */
+export function fromBytes(bytes: Uint8Array): string;
+
+/**
+ * Returns the decoded fields of a UUID
+ */
+export function inspect(uuid: string): UuidDetails;
+
+/**
+ * Parses a UUID in any of the supported formats and returns it in the 8-4-4-4-12
+ * format
+ */
+export function parse(uuid: string): string;
+
+/**
+ * Returns the 16 octets of a UUID, most significant first
+ */
+export function toBytes(uuid: string): Uint8Array;
+
+/**
+ * Formats a UUID as a 32-length hex string without dashes
+ */
+export function toHexJoined(uuid: string): string;
+
+/**
+ * Generates a time based (V1) UUID
+ */
+export function v1(): string;
+
+/**
+ * Generates a random (V4) UUID
+ */
+export function v4(): string;
+
+/**
+ * Generates a name based (V5) UUID from a string name. A random namespace is used
+ * if none is given
+ */
+export function v5(name: string, namespace?: string | null): string;
+
+/**
+ * Generates a name based (V5) UUID from a binary name. A random namespace is used
+ * if none is given
+ */
+export function v5FromBytes(name: Uint8Array, namespace?: string | null): string;
+
+/**
+ * Generates a Unix Epoch time based (V7) UUID
+ */
+export function v7(): string;
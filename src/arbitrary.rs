@@ -0,0 +1,32 @@
+//! `arbitrary::Arbitrary` for fuzzing, enabled by the `arbitrary` feature.
+//!
+//! Any 128-bit value is produced, so the UUIDs cover every version and variant,
+//! including the invalid ones.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::uuid::Uuid;
+
+impl<'a> Arbitrary<'a> for Uuid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Uuid::from_value(u128::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u128::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary() {
+        let bytes = 0x6ba7b8109dad11d180b400c04fd430c8_u128.to_le_bytes();
+        let mut u = Unstructured::new(&bytes);
+
+        assert_eq!(Uuid::arbitrary(&mut u).unwrap(), crate::wellknown::NS_DNS);
+        assert_eq!(Uuid::size_hint(0), (16, Some(16)));
+    }
+}
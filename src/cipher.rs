@@ -0,0 +1,172 @@
+//! Reversible encryption of UUIDs, to expose time ordered IDs without revealing their
+//! creation time or node ID.
+//!
+//! [`UuidCipher`] encrypts UUIDs of one version (e.g. V7) into opaque UUIDs that look
+//! like random V4 UUIDs, and decrypts them back. Both have 122 bits besides the
+//! version and variant, which are encrypted with AES-128. Since AES works on 128-bit
+//! blocks, it is applied repeatedly until the result fits in 122 bits ("cycle
+//! walking"), which takes 64 rounds on average.
+//!
+//! ```
+//! use uuidland::cipher::UuidCipher;
+//! use uuidland::uuid;
+//!
+//! let cipher = UuidCipher::new(&[0x2b; 16], 7);
+//!
+//! let internal = uuid!("01890a5d-ac96-774b-bcce-b302099a8057");
+//! let external = cipher.encrypt(&internal).unwrap();
+//!
+//! assert_eq!(external.details().version, 4);
+//! assert_eq!(cipher.decrypt(&external), Ok(internal));
+//! ```
+
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes128;
+
+use crate::uuid::Uuid;
+
+/// Mask of the 122 bits that are neither version nor variant bits
+const PAYLOAD_MASK: u128 = (1 << 122) - 1;
+
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CipherError {
+    #[error("Expected a version {expected} UUID, found version {found}")]
+    UnexpectedVersion { expected: u8, found: u8 },
+
+    #[error("Only UUIDs of the RFC 9562 variant can be encrypted or decrypted")]
+    UnexpectedVariant,
+}
+
+/// Encrypts UUIDs of a given version into V4 UUIDs with a secret key, and back
+#[derive(Clone)]
+pub struct UuidCipher {
+    cipher: Aes128,
+    version: u8,
+}
+
+impl UuidCipher {
+    /// Creates a cipher for UUIDs of the given version. Panics if the version doesn't
+    /// fit in 4 bits
+    pub fn new(key: &[u8; 16], version: u8) -> Self {
+        assert!(version <= 0xf, "The version is a 4-bit field");
+
+        Self {
+            cipher: Aes128::new(key.into()),
+            version,
+        }
+    }
+
+    /// Encrypts a UUID of the cipher's version into a V4 UUID
+    pub fn encrypt(&self, uuid: &Uuid) -> Result<Uuid, CipherError> {
+        let mut payload = payload_of(uuid, self.version)?;
+
+        loop {
+            let mut block = payload.to_be_bytes().into();
+            self.cipher.encrypt_block(&mut block);
+            payload = u128::from_be_bytes(block.into());
+
+            if payload <= PAYLOAD_MASK {
+                return Ok(Uuid::from_payload(payload, 4));
+            }
+        }
+    }
+
+    /// Decrypts a V4 UUID returned by [`UuidCipher::encrypt`]
+    pub fn decrypt(&self, uuid: &Uuid) -> Result<Uuid, CipherError> {
+        let mut payload = payload_of(uuid, 4)?;
+
+        loop {
+            let mut block = payload.to_be_bytes().into();
+            self.cipher.decrypt_block(&mut block);
+            payload = u128::from_be_bytes(block.into());
+
+            if payload <= PAYLOAD_MASK {
+                return Ok(Uuid::from_payload(payload, self.version));
+            }
+        }
+    }
+}
+
+/// Returns the payload of `uuid`, see [`Uuid::payload`], after checking its version
+/// and variant
+fn payload_of(uuid: &Uuid, version: u8) -> Result<u128, CipherError> {
+    let details = uuid.details();
+
+    if details.variant != 0x80 {
+        return Err(CipherError::UnexpectedVariant);
+    }
+
+    if details.version != version {
+        return Err(CipherError::UnexpectedVersion {
+            expected: version,
+            found: details.version,
+        });
+    }
+
+    Ok(uuid.payload())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload() {
+        let uuid = crate::uuid!("01890a5d-ac96-774b-bcce-b302099a8057");
+        let payload = payload_of(&uuid, 7).unwrap();
+
+        assert!(payload <= PAYLOAD_MASK);
+        assert_eq!(Uuid::from_payload(payload, 7), uuid);
+        assert_eq!(Uuid::from_payload(PAYLOAD_MASK, 4), crate::uuid!("ffffffff-ffff-4fff-bfff-ffffffffffff"));
+        assert_eq!(Uuid::from_payload(0, 7), crate::uuid!("00000000-0000-7000-8000-000000000000"));
+    }
+
+    #[test]
+    fn test_encrypt() {
+        // AES-128 key from FIPS 197 Appendix A.1
+        let key = 0x2b7e151628aed2a6abf7158809cf4f3c_u128.to_be_bytes();
+        let cipher = UuidCipher::new(&key, 7);
+
+        let internal = crate::uuid!("01890a5d-ac96-774b-bcce-b302099a8057");
+        let external = cipher.encrypt(&internal).unwrap();
+
+        // Computed with the AES implementation of Python's cryptography package
+        assert_eq!(external, crate::uuid!("582dc70a-cd4f-421a-83fd-c1d652b7ff1f"));
+        assert_eq!(cipher.decrypt(&external), Ok(internal));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = UuidCipher::new(&[0x42; 16], 1);
+        let other = UuidCipher::new(&[0x43; 16], 1);
+
+        for i in 0..1000u128 {
+            let internal = Uuid::from_payload(i.wrapping_mul(0x9e3779b97f4a7c15f39cc0605cedc834) & PAYLOAD_MASK, 1);
+            let external = cipher.encrypt(&internal).unwrap();
+
+            assert_eq!(external.details().version, 4);
+            assert_eq!(external.details().variant, 0x80);
+            assert_eq!(cipher.decrypt(&external), Ok(internal.clone()));
+            assert!(other.decrypt(&external) != Ok(internal));
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let cipher = UuidCipher::new(&[0x42; 16], 7);
+
+        assert_eq!(
+            cipher.encrypt(&crate::wellknown::NS_DNS),
+            Err(CipherError::UnexpectedVersion { expected: 7, found: 1 })
+        );
+        assert_eq!(
+            cipher.decrypt(&crate::wellknown::NS_DNS),
+            Err(CipherError::UnexpectedVersion { expected: 4, found: 1 })
+        );
+        assert_eq!(cipher.encrypt(&crate::wellknown::Nil), Err(CipherError::UnexpectedVariant));
+        assert_eq!(
+            cipher.encrypt(&crate::uuid!("01890a5d-ac96-774b-ccce-b302099a8057")),
+            Err(CipherError::UnexpectedVariant)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! `const fn` implementations of MD5 and SHA-1, used to generate V3 and V5
+//! UUIDs at compile time. These are only meant for hashing the short
+//! `namespace || name` messages of name based UUIDs; at runtime the `md-5` and
+//! `sha1` crates are used instead.
+
+/// The message hashed for a name based UUID: the 16 octets of the namespace
+/// followed by the name
+struct Message<'a> {
+    namespace: [u8; 16],
+    name: &'a [u8],
+}
+
+impl<'a> Message<'a> {
+    const fn len(&self) -> usize {
+        16 + self.name.len()
+    }
+
+    /// Length of the message after padding. The padding is a single 0x80 octet,
+    /// followed by zeros and the 64-bit message length so that the total is a
+    /// multiple of 64 octets
+    const fn padded_len(&self) -> usize {
+        (self.len() + 8) / 64 * 64 + 64
+    }
+
+    /// Returns the octet at index `i` of the padded message. MD5 stores the length
+    /// in little-endian order while SHA-1 uses big-endian order
+    const fn octet(&self, i: usize, big_endian_len: bool) -> u8 {
+        let len = self.len();
+        let padded_len = self.padded_len();
+
+        if i < 16 {
+            self.namespace[i]
+        } else if i < len {
+            self.name[i - 16]
+        } else if i == len {
+            0x80
+        } else if i >= padded_len - 8 {
+            let bit_len = (len as u64 * 8).to_le_bytes();
+            let k = i - (padded_len - 8);
+
+            if big_endian_len {
+                bit_len[7 - k]
+            } else {
+                bit_len[k]
+            }
+        } else {
+            0
+        }
+    }
+}
+
+const MD5_SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, //
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, //
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, //
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const MD5_CONSTANTS: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// MD5 of `namespace || name`, as used by V3 UUIDs
+pub(crate) const fn md5(namespace: [u8; 16], name: &[u8]) -> [u8; 16] {
+    let message = Message { namespace, name };
+
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+    let mut chunk = 0;
+    while chunk < message.padded_len() {
+        let mut words = [0u32; 16];
+        let mut i = 0;
+        while i < 16 {
+            let at = chunk + i * 4;
+            words[i] = u32::from_le_bytes([
+                message.octet(at, false),
+                message.octet(at + 1, false),
+                message.octet(at + 2, false),
+                message.octet(at + 3, false),
+            ]);
+            i += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d] = state;
+
+        let mut i = 0;
+        while i < 64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+
+            let f = f
+                .wrapping_add(a)
+                .wrapping_add(MD5_CONSTANTS[i])
+                .wrapping_add(words[g]);
+
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(MD5_SHIFTS[i]));
+
+            i += 1;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+
+        chunk += 64;
+    }
+
+    let mut digest = [0u8; 16];
+    let mut i = 0;
+    while i < 4 {
+        let bytes = state[i].to_le_bytes();
+        digest[i * 4] = bytes[0];
+        digest[i * 4 + 1] = bytes[1];
+        digest[i * 4 + 2] = bytes[2];
+        digest[i * 4 + 3] = bytes[3];
+        i += 1;
+    }
+
+    digest
+}
+
+/// SHA-1 of `namespace || name`, truncated to 128 bits as used by V5 UUIDs
+pub(crate) const fn sha1(namespace: [u8; 16], name: &[u8]) -> [u8; 16] {
+    let message = Message { namespace, name };
+
+    let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    let mut chunk = 0;
+    while chunk < message.padded_len() {
+        let mut words = [0u32; 80];
+        let mut i = 0;
+        while i < 16 {
+            let at = chunk + i * 4;
+            words[i] = u32::from_be_bytes([
+                message.octet(at, true),
+                message.octet(at + 1, true),
+                message.octet(at + 2, true),
+                message.octet(at + 3, true),
+            ]);
+            i += 1;
+        }
+        while i < 80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+            i += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+
+        let mut i = 0;
+        while i < 80 {
+            let (f, k) = match i / 20 {
+                0 => ((b & c) | (!b & d), 0x5a827999),
+                1 => (b ^ c ^ d, 0x6ed9eba1),
+                2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(words[i]);
+
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+
+            i += 1;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+
+        chunk += 64;
+    }
+
+    let mut digest = [0u8; 16];
+    let mut i = 0;
+    while i < 4 {
+        let bytes = state[i].to_be_bytes();
+        digest[i * 4] = bytes[0];
+        digest[i * 4 + 1] = bytes[1];
+        digest[i * 4 + 2] = bytes[2];
+        digest[i * 4 + 3] = bytes[3];
+        i += 1;
+    }
+
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use digest::Digest;
+
+    #[test]
+    fn test_against_digest() {
+        let namespace = *b"0123456789abcdef";
+        let name: Vec<u8> = (0..=255u8).collect();
+
+        // Cover message lengths around the 64-octet block and padding boundaries
+        for len in 0..name.len() {
+            let name = &name[..len];
+
+            let mut md5 = md5::Md5::new();
+            md5.update(namespace);
+            md5.update(name);
+            assert_eq!(super::md5(namespace, name), md5.finalize()[..]);
+
+            let mut sha1 = sha1::Sha1::new();
+            sha1.update(namespace);
+            sha1.update(name);
+            assert_eq!(super::sha1(namespace, name), sha1.finalize()[..16]);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Database integrations, storing [`Uuid`] in native UUID columns or as 16 octets.
+//!
+//! Each integration is enabled by its own feature:
+//!
+//! * `postgres-types`: `ToSql` and `FromSql` for the `postgres` and `tokio-postgres`
+//!   crates, using the Postgres `uuid` type
+//! * `sqlx-postgres`: `Type`, `Encode` and `Decode` for Postgres `uuid` columns, in
+//!   both the binary and text formats
+//! * `sqlx-mysql`: `Type`, `Encode` and `Decode` for MySQL `BINARY(16)` columns.
+//!   Values read from text columns are parsed as UUID strings
+//! * `sqlx-sqlite`: `Type`, `Encode` and `Decode` for SQLite, stored as a 16 octet
+//!   blob. Values read from text columns are parsed as UUID strings
+//! * `rusqlite`: `ToSql` and `FromSql` for the `rusqlite` crate, with the same
+//!   representation as `sqlx-sqlite`
+//!
+//! UUIDs are always written as their 16 octets in big-endian order. Columns filled by
+//! MySQL's `UUID_TO_BIN(uuid, 1)` have their time fields swapped; read them as bytes
+//! and convert them with [`Uuid::from_mysql_swapped_bytes`].
+
+#[cfg(feature = "postgres-types")]
+mod postgres;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+mod sqlx;
+
+use crate::uuid::Uuid;
+
+/// Error returned when a database value can't be read as a UUID
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum FromSqlError {
+    #[error("A binary UUID must be 16 bytes long, found {0} bytes")]
+    InvalidLength(usize),
+
+    #[error("Invalid UUID string")]
+    InvalidString,
+}
+
+/// Reads the 16 octets of a binary UUID
+fn from_binary(value: &[u8]) -> Result<Uuid, FromSqlError> {
+    let octets: [u8; 16] = value
+        .try_into()
+        .map_err(|_| FromSqlError::InvalidLength(value.len()))?;
+
+    Ok(Uuid::from_value(u128::from_be_bytes(octets)))
+}
+
+/// Parses a UUID stored as a string
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-mysql", feature = "sqlx-sqlite", feature = "rusqlite"))]
+fn from_text(value: &str) -> Result<Uuid, FromSqlError> {
+    Uuid::parse(value).map_err(|_| FromSqlError::InvalidString)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_binary() {
+        let uuid = crate::wellknown::NS_DNS;
+
+        assert_eq!(from_binary(&uuid.value().to_be_bytes()), Ok(uuid.clone()));
+        assert_eq!(from_binary(&[0; 15]), Err(FromSqlError::InvalidLength(15)));
+        assert_eq!(from_binary(b"6ba7b810-9dad-11d1-80b4-00c04fd430c8"), Err(FromSqlError::InvalidLength(36)));
+    }
+
+    #[cfg(any(feature = "sqlx-postgres", feature = "sqlx-mysql", feature = "sqlx-sqlite", feature = "rusqlite"))]
+    #[test]
+    fn test_from_text() {
+        assert_eq!(from_text("6ba7b810-9dad-11d1-80b4-00c04fd430c8"), Ok(crate::wellknown::NS_DNS));
+        assert_eq!(from_text("6ba7b810"), Err(FromSqlError::InvalidString));
+        assert_eq!(from_text("abcdefghijklmnop"), Err(FromSqlError::InvalidString));
+    }
+}
@@ -0,0 +1,58 @@
+//! `ToSql` and `FromSql` for the `postgres` and `tokio-postgres` crates
+
+use std::error::Error;
+
+use bytes::{BufMut, BytesMut};
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::uuid::Uuid;
+
+impl ToSql for Uuid {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.put_slice(&self.value().to_be_bytes());
+
+        Ok(IsNull::No)
+    }
+
+    accepts!(UUID);
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Uuid {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(super::from_binary(raw)?)
+    }
+
+    accepts!(UUID);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The binary wire format of the uuid type, as sent by uuid_send()
+    const NS_DNS_WIRE: [u8; 16] = [
+        0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8,
+    ];
+
+    #[test]
+    fn test_to_sql() {
+        let mut out = BytesMut::new();
+        let is_null = crate::wellknown::NS_DNS.to_sql_checked(&Type::UUID, &mut out).unwrap();
+
+        assert!(matches!(is_null, IsNull::No));
+        assert_eq!(out[..], NS_DNS_WIRE);
+
+        assert!(crate::wellknown::NS_DNS.to_sql_checked(&Type::TEXT, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_from_sql() {
+        assert_eq!(Uuid::from_sql(&Type::UUID, &NS_DNS_WIRE).unwrap(), crate::wellknown::NS_DNS);
+        assert!(Uuid::from_sql(&Type::UUID, &NS_DNS_WIRE[1..]).is_err());
+
+        assert!(<Uuid as FromSql>::accepts(&Type::UUID));
+        assert!(!<Uuid as FromSql>::accepts(&Type::BYTEA));
+    }
+}
@@ -0,0 +1,67 @@
+//! `ToSql` and `FromSql` for the `rusqlite` crate
+
+use alloc::boxed::Box;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::uuid::Uuid;
+
+impl ToSql for Uuid {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.value().to_be_bytes().to_vec()))
+    }
+}
+
+impl FromSql for Uuid {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Blob(blob) if blob.len() != 16 => Err(FromSqlError::InvalidBlobSize {
+                expected_size: 16,
+                blob_size: blob.len(),
+            }),
+            ValueRef::Blob(blob) => super::from_binary(blob).map_err(|err| FromSqlError::Other(Box::new(err))),
+            ValueRef::Text(text) => core::str::from_utf8(text)
+                .map_err(|err| FromSqlError::Other(Box::new(err)))
+                .and_then(|text| super::from_text(text).map_err(|err| FromSqlError::Other(Box::new(err)))),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_round_trip() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute("CREATE TABLE ids (id BLOB NOT NULL)", ()).unwrap();
+
+        let uuid = crate::wellknown::NS_URL;
+        connection.execute("INSERT INTO ids VALUES (?1)", [&uuid]).unwrap();
+
+        let stored: Vec<u8> = connection.query_row("SELECT id FROM ids", (), |row| row.get(0)).unwrap();
+        assert_eq!(stored, uuid.value().to_be_bytes());
+
+        let read: Uuid = connection.query_row("SELECT id FROM ids", (), |row| row.get(0)).unwrap();
+        assert_eq!(read, uuid);
+    }
+
+    #[test]
+    fn test_from_text_and_invalid() {
+        let connection = Connection::open_in_memory().unwrap();
+
+        let read: Uuid = connection
+            .query_row("SELECT '6ba7b811-9dad-11d1-80b4-00c04fd430c8'", (), |row| row.get(0))
+            .unwrap();
+        assert_eq!(read, crate::wellknown::NS_URL);
+
+        let invalid = |sql: &str| connection.query_row(sql, (), |row| row.get::<_, Uuid>(0)).is_err();
+        assert!(invalid("SELECT x'00'"));
+        assert!(invalid("SELECT 'not a uuid'"));
+        assert!(invalid("SELECT 'abcdefghijklmnop'"));
+        assert!(invalid("SELECT 42"));
+        assert!(invalid("SELECT NULL"));
+    }
+}
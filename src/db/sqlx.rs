@@ -0,0 +1,265 @@
+//! `Type`, `Encode` and `Decode` for the `sqlx` crate
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres {
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::postgres::types::Oid;
+    use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+    use sqlx::{Decode, Encode, Type};
+
+    use crate::uuid::Uuid;
+
+    /// OIDs of the `uuid` and `uuid[]` types, from pg_type.dat
+    const UUID_OID: Oid = Oid(2950);
+    const UUID_ARRAY_OID: Oid = Oid(2951);
+
+    impl Type<Postgres> for Uuid {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_oid(UUID_OID)
+        }
+    }
+
+    impl PgHasArrayType for Uuid {
+        fn array_type_info() -> PgTypeInfo {
+            PgTypeInfo::with_oid(UUID_ARRAY_OID)
+        }
+    }
+
+    impl Encode<'_, Postgres> for Uuid {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            buf.extend_from_slice(&self.value().to_be_bytes());
+
+            Ok(IsNull::No)
+        }
+    }
+
+    impl Decode<'_, Postgres> for Uuid {
+        fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+            decode_value(value.format(), value.as_bytes()?)
+        }
+    }
+
+    /// Decodes the raw bytes of a `uuid` value sent in the given format
+    fn decode_value(format: PgValueFormat, bytes: &[u8]) -> Result<Uuid, BoxDynError> {
+        let uuid = match format {
+            PgValueFormat::Binary => super::super::from_binary(bytes)?,
+            PgValueFormat::Text => super::super::from_text(std::str::from_utf8(bytes)?)?,
+        };
+
+        Ok(uuid)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::db::FromSqlError;
+
+        #[test]
+        fn test_encode() {
+            let mut buf = PgArgumentBuffer::default();
+            let is_null = Encode::<Postgres>::encode_by_ref(&crate::wellknown::NS_OID, &mut buf).unwrap();
+
+            assert!(!is_null.is_null());
+            assert_eq!(
+                buf[..],
+                [0x6b, 0xa7, 0xb8, 0x12, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8]
+            );
+
+            assert_eq!(<Uuid as Type<Postgres>>::type_info().oid(), Some(UUID_OID));
+            assert_eq!(Uuid::array_type_info().oid(), Some(UUID_ARRAY_OID));
+        }
+
+        #[test]
+        fn test_decode() {
+            let binary = [0x6b, 0xa7, 0xb8, 0x12, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];
+            assert_eq!(decode_value(PgValueFormat::Binary, &binary).unwrap(), crate::wellknown::NS_OID);
+
+            let text = b"6ba7b812-9dad-11d1-80b4-00c04fd430c8";
+            assert_eq!(decode_value(PgValueFormat::Text, text).unwrap(), crate::wellknown::NS_OID);
+
+            let error = decode_value(PgValueFormat::Binary, &binary[..15]).unwrap_err();
+            assert_eq!(error.downcast_ref(), Some(&FromSqlError::InvalidLength(15)));
+
+            let error = decode_value(PgValueFormat::Binary, text).unwrap_err();
+            assert_eq!(error.downcast_ref(), Some(&FromSqlError::InvalidLength(36)));
+
+            // A text value of 16 characters is not mistaken for 16 octets
+            let error = decode_value(PgValueFormat::Text, b"abcdefghijklmnop").unwrap_err();
+            assert_eq!(error.downcast_ref(), Some(&FromSqlError::InvalidString));
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-mysql")]
+mod mysql {
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+    use sqlx::{Decode, Encode, Type, ValueRef};
+
+    use crate::uuid::Uuid;
+
+    /// UUIDs are stored in `BINARY(16)` columns, but can be read from text columns too
+    impl Type<MySql> for Uuid {
+        fn type_info() -> MySqlTypeInfo {
+            <&[u8] as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty: &MySqlTypeInfo) -> bool {
+            <&[u8] as Type<MySql>>::compatible(ty) || <&str as Type<MySql>>::compatible(ty)
+        }
+    }
+
+    impl Encode<'_, MySql> for Uuid {
+        fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+            Encode::<MySql>::encode_by_ref(&&self.value().to_be_bytes()[..], buf)
+        }
+    }
+
+    impl Decode<'_, MySql> for Uuid {
+        fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+            let type_info = value.type_info().into_owned();
+            decode_value(&type_info, <&[u8] as Decode<MySql>>::decode(value)?)
+        }
+    }
+
+    /// Decodes the raw bytes of a column of the given type
+    fn decode_value(type_info: &MySqlTypeInfo, bytes: &[u8]) -> Result<Uuid, BoxDynError> {
+        let uuid = if <&str as Type<MySql>>::compatible(type_info) {
+            super::super::from_text(std::str::from_utf8(bytes)?)?
+        } else {
+            super::super::from_binary(bytes)?
+        };
+
+        Ok(uuid)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::db::FromSqlError;
+
+        #[test]
+        fn test_encode() {
+            let mut buf = Vec::new();
+            let is_null = Encode::<MySql>::encode_by_ref(&crate::wellknown::NS_X500, &mut buf).unwrap();
+
+            assert!(!is_null.is_null());
+
+            // A length-encoded string: the length as a single octet, then the 16 octets
+            assert_eq!(
+                buf,
+                [16, 0x6b, 0xa7, 0xb8, 0x14, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8]
+            );
+        }
+
+        #[test]
+        fn test_decode() {
+            let binary_type = <&[u8] as Type<MySql>>::type_info();
+            let text_type = <&str as Type<MySql>>::type_info();
+
+            let binary = [0x6b, 0xa7, 0xb8, 0x14, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];
+            assert_eq!(decode_value(&binary_type, &binary).unwrap(), crate::wellknown::NS_X500);
+
+            let text = b"6ba7b814-9dad-11d1-80b4-00c04fd430c8";
+            assert_eq!(decode_value(&text_type, text).unwrap(), crate::wellknown::NS_X500);
+
+            let error = decode_value(&binary_type, &binary[..15]).unwrap_err();
+            assert_eq!(error.downcast_ref(), Some(&FromSqlError::InvalidLength(15)));
+
+            // A text column of 16 characters is not mistaken for 16 octets
+            let error = decode_value(&text_type, b"abcdefghijklmnop").unwrap_err();
+            assert_eq!(error.downcast_ref(), Some(&FromSqlError::InvalidString));
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite {
+    use std::borrow::Cow;
+
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+    use sqlx::{Decode, Encode, Type, ValueRef};
+
+    use crate::uuid::Uuid;
+
+    /// UUIDs are stored as blobs, but can be read from text columns too
+    impl Type<Sqlite> for Uuid {
+        fn type_info() -> SqliteTypeInfo {
+            <&[u8] as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <&[u8] as Type<Sqlite>>::compatible(ty) || <&str as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for Uuid {
+        fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> Result<IsNull, BoxDynError> {
+            args.push(SqliteArgumentValue::Blob(Cow::Owned(self.value().to_be_bytes().to_vec())));
+
+            Ok(IsNull::No)
+        }
+    }
+
+    impl Decode<'_, Sqlite> for Uuid {
+        fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+            let uuid = if <&str as Type<Sqlite>>::compatible(&value.type_info()) {
+                super::super::from_text(<&str as Decode<Sqlite>>::decode(value)?)?
+            } else {
+                super::super::from_binary(<&[u8] as Decode<Sqlite>>::decode(value)?)?
+            };
+
+            Ok(uuid)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use sqlx::{Connection, SqliteConnection};
+
+        use crate::uuid::Uuid;
+
+        #[tokio::test]
+        async fn test_round_trip() {
+            let mut connection = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            sqlx::query("CREATE TABLE ids (id BLOB NOT NULL)")
+                .execute(&mut connection)
+                .await
+                .unwrap();
+
+            let uuid = crate::wellknown::NS_DNS;
+            sqlx::query("INSERT INTO ids VALUES (?)")
+                .bind(&uuid)
+                .execute(&mut connection)
+                .await
+                .unwrap();
+
+            let stored: Vec<u8> = sqlx::query_scalar("SELECT id FROM ids")
+                .fetch_one(&mut connection)
+                .await
+                .unwrap();
+            assert_eq!(stored, uuid.value().to_be_bytes());
+
+            let read: Uuid = sqlx::query_scalar("SELECT id FROM ids")
+                .fetch_one(&mut connection)
+                .await
+                .unwrap();
+            assert_eq!(read, uuid);
+
+            let text: Uuid = sqlx::query_scalar("SELECT '6ba7b810-9dad-11d1-80b4-00c04fd430c8'")
+                .fetch_one(&mut connection)
+                .await
+                .unwrap();
+            assert_eq!(text, uuid);
+
+            for invalid in ["SELECT x'0102'", "SELECT 'abcdefghijklmnop'"] {
+                let invalid = sqlx::query_scalar::<_, Uuid>(invalid).fetch_one(&mut connection).await;
+                assert!(invalid.is_err());
+            }
+        }
+    }
+}
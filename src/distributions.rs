@@ -0,0 +1,138 @@
+//! Distributions for sampling UUIDs with `rand`.
+//!
+//! The [`Standard`] distribution produces V4 UUIDs, so `rng.gen::<Uuid>()` is the same
+//! as [`gen::v4_with_rng`](crate::gen::v4_with_rng). The distributions in this module
+//! produce UUIDs of a given version, such as V7 UUIDs with a timestamp in a range:
+//!
+//! ```
+//! use rand::Rng;
+//! use rand::rngs::mock::StepRng;
+//! use uuidland::distributions::V7;
+//! use uuidland::inspect::Timestamp;
+//!
+//! let mut rng = StepRng::new(1, 1);
+//! let uuid = rng.sample(V7::new(1_700_000_000_000, 1_800_000_000_000));
+//!
+//! assert!(matches!(uuid.details().timestamp(), Some(Timestamp::UnixMillis(1_700_000_000_000..))));
+//! ```
+
+use rand::distributions::{Distribution, Standard, Uniform};
+use rand::Rng;
+
+use crate::uuid::{Octets, Uuid};
+
+impl Distribution<Uuid> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Uuid {
+        crate::uuid_v4::v4_with_rng(rng)
+    }
+}
+
+/// Distribution of random V4 UUIDs, the same as [`Standard`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct V4;
+
+impl Distribution<Uuid> for V4 {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Uuid {
+        crate::uuid_v4::v4_with_rng(rng)
+    }
+}
+
+/// Distribution of V7 UUIDs with a timestamp uniformly distributed in a range of Unix
+/// milliseconds. The `rand_a` and `rand_b` fields are random
+#[derive(Clone, Copy, Debug)]
+pub struct V7 {
+    unix_millis: Uniform<u64>,
+}
+
+impl V7 {
+    /// The largest timestamp of a V7 UUID, which stores 48 bits
+    pub const MAX_UNIX_MILLIS: u64 = (1 << 48) - 1;
+
+    /// Samples timestamps in `[low, high)`. Panics if `low >= high`, or if `high - 1`
+    /// is larger than [`V7::MAX_UNIX_MILLIS`]
+    pub fn new(low: u64, high: u64) -> Self {
+        assert!(high.saturating_sub(1) <= Self::MAX_UNIX_MILLIS, "V7 timestamps are limited to 48 bits");
+
+        Self {
+            unix_millis: Uniform::new(low, high),
+        }
+    }
+
+    /// Samples timestamps in `[low, high]`. Panics if `low > high`, or if `high` is
+    /// larger than [`V7::MAX_UNIX_MILLIS`]
+    pub fn new_inclusive(low: u64, high: u64) -> Self {
+        assert!(high <= Self::MAX_UNIX_MILLIS, "V7 timestamps are limited to 48 bits");
+
+        Self {
+            unix_millis: Uniform::new_inclusive(low, high),
+        }
+    }
+}
+
+impl Distribution<Uuid> for V7 {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Uuid {
+        let msec = self.unix_millis.sample(rng);
+
+        let mut octets = Octets::default();
+        rng.fill_bytes(&mut octets[6..=15]);
+
+        // 48-bit big-endian Unix timestamp in milliseconds
+        octets[0..=5].copy_from_slice(&msec.to_be_bytes()[2..=7]);
+
+        Uuid::from_octets(octets, 7)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::inspect::Timestamp;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_standard() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..100 {
+            let details = rng.gen::<Uuid>().details();
+
+            assert_eq!(details.version, 4);
+            assert_eq!(details.variant, 0x80);
+        }
+
+        assert!(rng.gen::<Uuid>() != rng.gen::<Uuid>());
+        assert_eq!(rng.sample(V4).details().version, 4);
+    }
+
+    #[test]
+    fn test_v7() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let distribution = V7::new(1_000, 1_010);
+        let mut seen = [false; 10];
+
+        for _ in 0..1000 {
+            let details = rng.sample(distribution).details();
+
+            assert_eq!(details.version, 7);
+            assert_eq!(details.variant, 0x80);
+
+            match details.timestamp() {
+                Some(Timestamp::UnixMillis(msec @ 1_000..=1_009)) => seen[(msec - 1_000) as usize] = true,
+                other => panic!("Unexpected timestamp {other:?}"),
+            }
+        }
+
+        assert!(seen.iter().all(|&seen| seen));
+
+        let max = rng.sample(V7::new_inclusive(V7::MAX_UNIX_MILLIS, V7::MAX_UNIX_MILLIS));
+        assert_eq!(max.details().timestamp(), Some(Timestamp::UnixMillis(V7::MAX_UNIX_MILLIS)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_v7_out_of_range() {
+        V7::new_inclusive(0, V7::MAX_UNIX_MILLIS + 1);
+    }
+}
@@ -0,0 +1,456 @@
+//! Compact text encodings of UUIDs, for URL slugs, QR codes and other places where
+//! the 36 characters of the 8-4-4-4-12 format are too long.
+//!
+//! | Encoding          | Length | Alphabet                                     |
+//! |-------------------|--------|----------------------------------------------|
+//! | Crockford Base32  | 26     | `0-9 A-Z` without `I L O U`, as used by ULID |
+//! | Base58            | 22     | Bitcoin alphabet, zero-padded with `1`       |
+//! | Base64url         | 22     | `A-Z a-z 0-9 - _` without padding            |
+//!
+//! [`ShortCodec`] additionally encodes UUIDs in any alphabet, compatible with the
+//! `shortuuid` Python library.
+//!
+//! Every encoding is fixed-length, and decoding is strict: input of the wrong
+//! length, with characters outside the alphabet, or encoding a value that does not
+//! fit in 128 bits is rejected, so that each UUID has exactly one encoded form.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::uuid::Uuid;
+
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BITCOIN_BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Error returned when decoding a UUID from one of the compact encodings fails
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("Expected {expected} characters, found {found}")]
+    InvalidLength { expected: usize, found: usize },
+
+    #[error("Invalid character {character:?} at index {index}")]
+    InvalidCharacter { character: char, index: usize },
+
+    #[error("Encoded value does not fit in 128 bits")]
+    Overflow,
+}
+
+/// Maps a character to its digit value in `alphabet`
+fn digit_of(alphabet: &[u8], character: u8) -> Option<u8> {
+    alphabet
+        .iter()
+        .position(|&ch| ch == character)
+        .map(|digit| digit as u8)
+}
+
+/// Splits `value` into `len` digits of the given radix, most significant first
+pub(crate) fn encode_radix(mut value: u128, alphabet: &[u8], len: usize) -> String {
+    let radix = alphabet.len() as u128;
+    let mut digits = alloc::vec![alphabet[0]; len];
+
+    for digit in digits.iter_mut().rev() {
+        *digit = alphabet[(value % radix) as usize];
+        value /= radix;
+    }
+
+    debug_assert_eq!(value, 0);
+
+    // Only characters from the alphabet are ever written
+    String::from_utf8(digits).unwrap()
+}
+
+/// Parses fixed-length digits in the radix of `alphabet`, most significant first
+pub(crate) fn decode_radix(input: &str, alphabet: &[u8], len: usize) -> Result<u128, DecodeError> {
+    check_length(input, len)?;
+
+    let radix = alphabet.len() as u128;
+    let mut value = 0u128;
+
+    for (index, ch) in input.bytes().enumerate() {
+        let digit = digit_of(alphabet, ch).ok_or_else(|| invalid_character(input, index))?;
+
+        value = value
+            .checked_mul(radix)
+            .and_then(|value| value.checked_add(digit as u128))
+            .ok_or(DecodeError::Overflow)?;
+    }
+
+    Ok(value)
+}
+
+/// Checks that `input` has `expected` characters, all of them ASCII. Afterwards byte
+/// indices of `input` are also character indices
+pub(crate) fn check_length(input: &str, expected: usize) -> Result<(), DecodeError> {
+    let found = input.chars().count();
+
+    if found != expected {
+        return Err(DecodeError::InvalidLength { expected, found });
+    }
+
+    match input.chars().enumerate().find(|(_, ch)| !ch.is_ascii()) {
+        Some((index, character)) => Err(DecodeError::InvalidCharacter { character, index }),
+        None => Ok(()),
+    }
+}
+
+/// The error for the character at `index` of an ASCII `input`
+pub(crate) fn invalid_character(input: &str, index: usize) -> DecodeError {
+    let character = input.as_bytes()[index] as char;
+    DecodeError::InvalidCharacter { character, index }
+}
+
+impl Uuid {
+    /// Encodes the UUID as 26 characters of Crockford's Base32, in upper case. This
+    /// is the same text representation as a ULID
+    ///
+    /// ```
+    /// use uuidland::Uuid;
+    ///
+    /// let uuid = Uuid::parse("01890a5d-ac96-774b-bcce-b302099a8057").unwrap();
+    /// assert_eq!(uuid.to_base32(), "01H455VB4PEX5VSKNK084SN02Q");
+    /// ```
+    pub fn to_base32(&self) -> String {
+        encode_radix(self.value(), CROCKFORD_BASE32, 26)
+    }
+
+    /// Decodes a UUID from 26 characters of Crockford's Base32. Both upper and lower
+    /// case are accepted, but the ambiguous characters `I`, `L`, `O` and `U` are not
+    pub fn from_base32(input: &str) -> Result<Self, DecodeError> {
+        check_length(input, 26)?;
+
+        let upper = input.bytes().map(|ch| ch.to_ascii_uppercase());
+        let mut value = 0u128;
+
+        for (index, ch) in upper.enumerate() {
+            let digit = digit_of(CROCKFORD_BASE32, ch).ok_or_else(|| invalid_character(input, index))?;
+
+            // 26 characters hold 130 bits, so the first may only hold 3 bits
+            if index == 0 && digit > 7 {
+                return Err(DecodeError::Overflow);
+            }
+
+            value = value << 5 | digit as u128;
+        }
+
+        Ok(Uuid::from_value(value))
+    }
+
+    /// Formats the UUID as a ULID. A ULID is the same 128-bit value as a UUID, written
+    /// in Crockford's Base32, so this is an alias of [`Uuid::to_base32`]
+    pub fn to_ulid_string(&self) -> String {
+        self.to_base32()
+    }
+
+    /// Parses a ULID into a UUID holding the same 128-bit value. The millisecond
+    /// timestamp of the ULID can be read back with [`UuidDetails::ulid_timestamp`]
+    ///
+    /// [`UuidDetails::ulid_timestamp`]: crate::inspect::UuidDetails::ulid_timestamp
+    pub fn from_ulid_str(input: &str) -> Result<Self, DecodeError> {
+        Self::from_base32(input)
+    }
+
+    /// Encodes the UUID as 22 characters of Base58, using the Bitcoin alphabet. The
+    /// output is padded with leading `1`s (the zero digit) to a fixed length
+    pub fn to_base58(&self) -> String {
+        encode_radix(self.value(), BITCOIN_BASE58, 22)
+    }
+
+    /// Decodes a UUID from 22 characters of Base58, using the Bitcoin alphabet
+    pub fn from_base58(input: &str) -> Result<Self, DecodeError> {
+        decode_radix(input, BITCOIN_BASE58, 22).map(Uuid::from_value)
+    }
+
+    /// Encodes the 16 octets of the UUID as 22 characters of unpadded Base64url
+    /// (RFC 4648 Section 5)
+    pub fn to_base64url(&self) -> String {
+        let value = self.value();
+        let mut output = String::with_capacity(22);
+
+        // The first 21 characters hold 126 bits, the last one the remaining 2
+        // bits followed by 4 zero bits
+        for i in 0..21 {
+            let sextet = (value >> (122 - 6 * i)) & 0x3f;
+            output.push(BASE64_URL[sextet as usize] as char);
+        }
+
+        output.push(BASE64_URL[((value & 0x03) << 4) as usize] as char);
+
+        output
+    }
+
+    /// Decodes a UUID from 22 characters of unpadded Base64url. Encodings whose
+    /// unused trailing bits are not zero are rejected
+    pub fn from_base64url(input: &str) -> Result<Self, DecodeError> {
+        check_length(input, 22)?;
+
+        let mut value = 0u128;
+
+        for (index, ch) in input.bytes().enumerate() {
+            let sextet = digit_of(BASE64_URL, ch).ok_or_else(|| invalid_character(input, index))?;
+
+            if index < 21 {
+                value = value << 6 | sextet as u128;
+            } else if sextet & 0x0f != 0 {
+                return Err(invalid_character(input, index));
+            } else {
+                value = value << 2 | (sextet >> 4) as u128;
+            }
+        }
+
+        Ok(Uuid::from_value(value))
+    }
+}
+
+/// Error returned by [`ShortCodec::new`] for alphabets with less than 2 distinct characters
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Alphabet must contain at least 2 distinct characters")]
+pub struct InvalidAlphabet;
+
+/// Encodes UUIDs as fixed-length strings over a custom alphabet, compatible with the
+/// `shortuuid` Python library.
+///
+/// Like `shortuuid`, the alphabet is sorted and duplicate characters are removed, and
+/// the output is padded with the first character of the alphabet to the number of
+/// characters needed for any 128-bit value. The default alphabet is that of
+/// `shortuuid`, which drops the look-alike characters `0`, `1`, `I`, `O` and `l`.
+///
+/// ```
+/// use uuidland::Uuid;
+/// use uuidland::encoding::ShortCodec;
+///
+/// let codec = ShortCodec::default();
+/// let uuid = Uuid::parse("3b1f8b40-222c-4a6e-b77e-779d5a94e21c").unwrap();
+///
+/// assert_eq!(codec.encode(&uuid), "CXc85b4rqinB7s5J52TRYb");
+/// assert_eq!(codec.decode("CXc85b4rqinB7s5J52TRYb"), Ok(uuid));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortCodec {
+    alphabet: Vec<char>,
+    length: usize,
+}
+
+impl ShortCodec {
+    /// The default alphabet of `shortuuid`
+    pub const DEFAULT_ALPHABET: &'static str =
+        "23456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    pub fn new(alphabet: &str) -> Result<Self, InvalidAlphabet> {
+        let mut alphabet: Vec<char> = alphabet.chars().collect();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+
+        if alphabet.len() < 2 {
+            return Err(InvalidAlphabet);
+        }
+
+        // The smallest length for which radix^length >= 2^128, i.e. the first
+        // power of the radix that overflows a u128
+        let radix = alphabet.len() as u128;
+        let mut length = 1;
+        let mut capacity = radix;
+
+        while let Some(next) = capacity.checked_mul(radix) {
+            capacity = next;
+            length += 1;
+        }
+
+        Ok(Self {
+            alphabet,
+            length: length + 1,
+        })
+    }
+
+    /// The sorted, deduplicated alphabet
+    pub fn alphabet(&self) -> String {
+        self.alphabet.iter().collect()
+    }
+
+    /// The number of characters of every encoded UUID
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn encode(&self, uuid: &Uuid) -> String {
+        let radix = self.alphabet.len() as u128;
+        let mut value = uuid.value();
+        let mut digits = alloc::vec![self.alphabet[0]; self.length];
+
+        for digit in digits.iter_mut().rev() {
+            *digit = self.alphabet[(value % radix) as usize];
+            value /= radix;
+        }
+
+        digits.into_iter().collect()
+    }
+
+    /// Decodes a UUID encoded by [`ShortCodec::encode`]. Unlike `shortuuid`, input
+    /// that is not exactly [`ShortCodec::length`] characters long is rejected
+    pub fn decode(&self, input: &str) -> Result<Uuid, DecodeError> {
+        let found = input.chars().count();
+
+        if found != self.length {
+            return Err(DecodeError::InvalidLength { expected: self.length, found });
+        }
+
+        let radix = self.alphabet.len() as u128;
+        let mut value = 0u128;
+
+        for (index, character) in input.chars().enumerate() {
+            let digit = self
+                .alphabet
+                .binary_search(&character)
+                .map_err(|_| DecodeError::InvalidCharacter { character, index })?;
+
+            value = value
+                .checked_mul(radix)
+                .and_then(|value| value.checked_add(digit as u128))
+                .ok_or(DecodeError::Overflow)?;
+        }
+
+        Ok(Uuid::from_value(value))
+    }
+}
+
+impl Default for ShortCodec {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_ALPHABET).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let uuid = Uuid::parse("ffb82219-2be8-4961-8c83-2163e1b4b966").unwrap();
+
+        assert_eq!(uuid.to_base32(), "7ZQ0H1JAZ895GRS0S1CFGV9EB6");
+        assert_eq!(uuid.to_base58(), "YaV4GVR3TqiyZKwr92kVZo");
+        assert_eq!(uuid.to_base64url(), "_7giGSvoSWGMgyFj4bS5Zg");
+
+        let nil = crate::wellknown::Nil;
+        assert_eq!(nil.to_base32(), "00000000000000000000000000");
+        assert_eq!(nil.to_base58(), "1111111111111111111111");
+        assert_eq!(nil.to_base64url(), "AAAAAAAAAAAAAAAAAAAAAA");
+
+        let max = Uuid::from_value(u128::MAX);
+        assert_eq!(max.to_base32(), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!(max.to_base58(), "YcVfxkQb6JRzqk5kF2tNLv");
+        assert_eq!(max.to_base64url(), "_____________________w");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = [0, 1, 0xff, u128::MAX, u128::MAX - 1, 1 << 127, 0x0123_4567_89ab_cdef];
+
+        for value in values.into_iter().chain((0..64).map(|i| 0x9e37_79b9_7f4a_7c15_u128.wrapping_pow(i))) {
+            let uuid = Uuid::from_value(value);
+
+            assert_eq!(Uuid::from_base32(&uuid.to_base32()), Ok(uuid.clone()));
+            assert_eq!(Uuid::from_base58(&uuid.to_base58()), Ok(uuid.clone()));
+            assert_eq!(Uuid::from_base64url(&uuid.to_base64url()), Ok(uuid.clone()));
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let uuid = Uuid::parse("ffb82219-2be8-4961-8c83-2163e1b4b966").unwrap();
+
+        assert_eq!(Uuid::from_base32("7zq0h1jaz895grs0s1cfgv9eb6"), Ok(uuid));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        use DecodeError::*;
+
+        assert_eq!(
+            Uuid::from_base32("7ZQ0H1JAZ895GRS0S1CFGV9EB"),
+            Err(InvalidLength { expected: 26, found: 25 })
+        );
+        assert_eq!(
+            Uuid::from_base32("7ZQ0H1JAZ895GRS0S1CFGV9EBU"),
+            Err(InvalidCharacter { character: 'U', index: 25 })
+        );
+        assert_eq!(Uuid::from_base32("8ZQ0H1JAZ895GRS0S1CFGV9EB6"), Err(Overflow));
+
+        assert_eq!(
+            Uuid::from_base58("YaV4GVR3TqiyZKwr92kVZ0"),
+            Err(InvalidCharacter { character: '0', index: 21 })
+        );
+        assert_eq!(Uuid::from_base58("zzzzzzzzzzzzzzzzzzzzzz"), Err(Overflow));
+        assert_eq!(
+            Uuid::from_base58("YaV4GVR3TqiyZKwr92kVZoo"),
+            Err(InvalidLength { expected: 22, found: 23 })
+        );
+
+        assert_eq!(
+            Uuid::from_base64url("_7giGSvoSWGMgyFj4bS5Z+"),
+            Err(InvalidCharacter { character: '+', index: 21 })
+        );
+        assert_eq!(
+            Uuid::from_base64url("_7giGSvoSWGMgyFj4bS5Zh"),
+            Err(InvalidCharacter { character: 'h', index: 21 })
+        );
+        assert_eq!(
+            Uuid::from_base64url("_7giGSvoSWGMgyFj4bS5Zg=="),
+            Err(InvalidLength { expected: 22, found: 24 })
+        );
+        assert_eq!(
+            Uuid::from_base64url("_7giGSvoSWGMgyFj4bS5Zé"),
+            Err(InvalidCharacter { character: 'é', index: 21 })
+        );
+    }
+
+    #[test]
+    fn test_short_codec() {
+        // Outputs of shortuuid 1.0
+        let uuid = Uuid::parse("3b1f8b40-222c-4a6e-b77e-779d5a94e21c").unwrap();
+
+        let codec = ShortCodec::default();
+        assert_eq!(codec.length(), 22);
+        assert_eq!(codec.encode(&uuid), "CXc85b4rqinB7s5J52TRYb");
+        assert_eq!(codec.decode("CXc85b4rqinB7s5J52TRYb"), Ok(uuid.clone()));
+        assert_eq!(codec.encode(&crate::wellknown::Nil), "2222222222222222222222");
+
+        // Alphabets are sorted and deduplicated
+        let codec = ShortCodec::new("01010101010101").unwrap();
+        assert_eq!(codec.alphabet(), "01");
+        assert_eq!(codec.length(), 128);
+        assert_eq!(codec.decode(&codec.encode(&uuid)), Ok(uuid.clone()));
+
+        let codec = ShortCodec::new("fedcba9876543210").unwrap();
+        assert_eq!(codec.encode(&uuid), uuid.to_string_hex_joined());
+
+        assert_eq!(ShortCodec::new("aaaa"), Err(InvalidAlphabet));
+    }
+
+    #[test]
+    fn test_short_codec_errors() {
+        use DecodeError::*;
+
+        let codec = ShortCodec::default();
+
+        assert_eq!(
+            codec.decode("CXc85b4rqinB7s5J52TRY"),
+            Err(InvalidLength { expected: 22, found: 21 })
+        );
+        assert_eq!(
+            codec.decode("CXc85b4rqinB7s5J52TRY0"),
+            Err(InvalidCharacter { character: '0', index: 21 })
+        );
+        assert_eq!(codec.decode("zzzzzzzzzzzzzzzzzzzzzz"), Err(Overflow));
+    }
+
+    #[test]
+    fn test_ulid() {
+        // Example from the ULID specification
+        let uuid = Uuid::from_ulid_str("01ARYZ6S41TSV4RRFFQ69G5FAV").unwrap();
+
+        assert_eq!(uuid.to_string_hex(), "01563df3-6481-d676-4c61-efb99302bd5b");
+        assert_eq!(uuid.to_ulid_string(), "01ARYZ6S41TSV4RRFFQ69G5FAV");
+        assert_eq!(Uuid::from_ulid_str("01aryz6s41tsv4rrffq69g5fav"), Ok(uuid));
+    }
+}
@@ -3,45 +3,293 @@ use crate::uuid::{Octets, Uuid};
 use digest::Digest;
 use md5::Md5;
 use sha1::Sha1;
+use sha2::{Sha256, Sha512};
 
-fn hash_based_uuid<D: Digest>(
-    mut hasher: D,
-    namespace: Option<Uuid>,
-    name: &[u8],
+/// Incrementally builds a name based UUID (V3, V5 or V8) from a namespace and a
+/// name that is fed in chunks, e.g. while streaming a large file.
+///
+/// ```
+/// use uuidland::{gen, wellknown, NameBasedBuilder};
+///
+/// let mut builder = NameBasedBuilder::v5(wellknown::NS_DNS);
+/// builder.update(b"foo");
+/// builder.update(b"bar");
+///
+/// assert_eq!(builder.finish(), gen::v5_with(b"foobar", wellknown::NS_DNS));
+/// ```
+///
+/// With the `std` feature enabled, the builder also implements `std::io::Write`, so
+/// it can be used as the sink of `std::io::copy`.
+///
+/// Like `v3_with`, `v5_with` and `v8_*_with`, the builder is also available on
+/// `no_std` targets.
+#[derive(Clone)]
+pub struct NameBasedBuilder<D> {
+    hasher: D,
     version_hi: u8,
-) -> Uuid {
-    let namespace = namespace.unwrap_or_else(|| crate::uuid_v4::v4());
-    hasher.update(namespace.value().to_be_bytes());
-    hasher.update(name);
-    let hash = hasher.finalize();
+}
+
+impl<D: Digest> NameBasedBuilder<D> {
+    fn new(namespace: Uuid, version_hi: u8) -> Self {
+        assert!(
+            <D as Digest>::output_size() >= 16,
+            "Hash output must be at least 128 bits long"
+        );
+
+        let mut hasher = D::new();
+        hasher.update(namespace.value().to_be_bytes());
+
+        Self { hasher, version_hi }
+    }
+
+    /// Appends `data` to the name
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    /// Consumes the builder and returns the UUID of the name fed so far
+    pub fn finish(self) -> Uuid {
+        let hash = self.hasher.finalize();
+        let octets: Octets = hash[0..=15].try_into().unwrap();
+
+        Uuid::from_octets(octets, self.version_hi)
+    }
+}
 
-    let octets: Octets = hash[0..=15].try_into().unwrap();
+impl<D: Digest> NameBasedBuilder<D> {
+    /// Creates a builder for a V8 UUID in the given namespace, using `D` as the
+    /// hash function. See [`v8_hash`] for details
+    pub fn v8(namespace: Uuid) -> Self {
+        Self::new(namespace, 0x08)
+    }
+}
 
-    Uuid::from_octets(octets, version_hi)
+impl NameBasedBuilder<Md5> {
+    /// Creates a builder for a V3 (MD5) UUID in the given namespace
+    pub fn v3(namespace: Uuid) -> Self {
+        Self::new(namespace, 0x03)
+    }
 }
 
+impl NameBasedBuilder<Sha1> {
+    /// Creates a builder for a V5 (SHA-1) UUID in the given namespace
+    pub fn v5(namespace: Uuid) -> Self {
+        Self::new(namespace, 0x05)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: Digest> std::io::Write for NameBasedBuilder<D> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn hash_based_uuid<D: Digest>(namespace: Uuid, name: &[u8], version_hi: u8) -> Uuid {
+    let mut builder = NameBasedBuilder::<D>::new(namespace, version_hi);
+    builder.update(name);
+    builder.finish()
+}
+
+/// Returns `namespace`, or a random V4 UUID when it is `None`
+#[cfg(feature = "std")]
+fn namespace_or_random(namespace: Option<Uuid>) -> Uuid {
+    namespace.unwrap_or_else(crate::uuid_v4::v4)
+}
+
+/// Generates a V3 (MD5) UUID from a name in the given namespace. A random namespace is
+/// used when `namespace` is `None`
+#[cfg(feature = "std")]
 pub fn v3(name: &[u8], namespace: Option<Uuid>) -> Uuid {
-    hash_based_uuid(<Md5 as Digest>::new(), namespace, name, 0x03)
+    v3_with(name, namespace_or_random(namespace))
 }
 
+/// Generates a V5 (SHA-1) UUID from a name in the given namespace. A random namespace
+/// is used when `namespace` is `None`
+#[cfg(feature = "std")]
 pub fn v5(name: &[u8], namespace: Option<Uuid>) -> Uuid {
-    hash_based_uuid(<Sha1 as Digest>::new(), namespace, name, 0x05)
+    v5_with(name, namespace_or_random(namespace))
+}
+
+/// Generates a V3 (MD5) UUID from a name in the given namespace. Unlike [`v3`], this
+/// is also available on `no_std` targets
+pub fn v3_with(name: &[u8], namespace: Uuid) -> Uuid {
+    hash_based_uuid::<Md5>(namespace, name, 0x03)
+}
+
+/// Generates a V5 (SHA-1) UUID from a name in the given namespace. Unlike [`v5`], this
+/// is also available on `no_std` targets
+///
+/// ```
+/// use uuidland::{gen, wellknown};
+///
+/// let uuid = gen::v5_with(b"foobar", wellknown::NS_DNS);
+/// assert_eq!(uuid.to_string_hex(), "a050b517-6677-5119-9a77-2d26bbf30507");
+/// ```
+pub fn v5_with(name: &[u8], namespace: Uuid) -> Uuid {
+    hash_based_uuid::<Sha1>(namespace, name, 0x05)
+}
+
+/// Generates a V3 UUID in a `const` context. The output is identical to that of
+/// [`v3_with`]; prefer the [`const_v3!`](crate::const_v3!) macro to compute it at compile time
+pub const fn v3_const(name: &[u8], namespace: Uuid) -> Uuid {
+    let hash = crate::const_hash::md5(namespace.value().to_be_bytes(), name);
+    Uuid::from_octets(hash, 0x03)
+}
+
+/// Generates a V5 UUID in a `const` context. The output is identical to that of
+/// [`v5_with`]; prefer the [`const_v5!`](crate::const_v5!) macro to compute it at compile time
+pub const fn v5_const(name: &[u8], namespace: Uuid) -> Uuid {
+    let hash = crate::const_hash::sha1(namespace.value().to_be_bytes(), name);
+    Uuid::from_octets(hash, 0x05)
+}
+
+/// Generates a name based V8 UUID using any hash function `D`, as described in
+/// RFC 9562 Appendix B.2. A random namespace is used when `namespace` is `None`.
+///
+/// The namespace and name are hashed in the same way as for V3 and V5, and the
+/// first 128 bits of the hash make up the UUID. `D` must produce an output of at
+/// least 128 bits, otherwise this function panics.
+///
+/// ```
+/// use uuidland::{gen, wellknown};
+/// use sha2::Sha384;
+///
+/// let uuid = gen::v8_hash::<Sha384>(b"www.example.com", Some(wellknown::NS_DNS));
+/// assert_eq!(uuid.details().version, 8);
+/// ```
+#[cfg(feature = "std")]
+pub fn v8_hash<D: Digest>(name: &[u8], namespace: Option<Uuid>) -> Uuid {
+    v8_hash_with::<D>(name, namespace_or_random(namespace))
+}
+
+/// Generates a name based V8 UUID using SHA-256. A random namespace is used when
+/// `namespace` is `None`
+#[cfg(feature = "std")]
+pub fn v8_sha256(name: &[u8], namespace: Option<Uuid>) -> Uuid {
+    v8_sha256_with(name, namespace_or_random(namespace))
+}
+
+/// Generates a name based V8 UUID using SHA-512. A random namespace is used when
+/// `namespace` is `None`
+#[cfg(feature = "std")]
+pub fn v8_sha512(name: &[u8], namespace: Option<Uuid>) -> Uuid {
+    v8_sha512_with(name, namespace_or_random(namespace))
+}
+
+/// Generates a name based V8 UUID in the given namespace using any hash function `D`.
+/// See [`v8_hash`] for details. Unlike it, this is also available on `no_std` targets
+pub fn v8_hash_with<D: Digest>(name: &[u8], namespace: Uuid) -> Uuid {
+    hash_based_uuid::<D>(namespace, name, 0x08)
+}
+
+/// Generates a name based V8 UUID in the given namespace using SHA-256
+pub fn v8_sha256_with(name: &[u8], namespace: Uuid) -> Uuid {
+    v8_hash_with::<Sha256>(name, namespace)
 }
 
+/// Generates a name based V8 UUID in the given namespace using SHA-512
+pub fn v8_sha512_with(name: &[u8], namespace: Uuid) -> Uuid {
+    v8_hash_with::<Sha512>(name, namespace)
+}
 
 #[cfg(test)]
 mod tests {
+    use super::NameBasedBuilder;
     use crate::wellknown;
 
     #[test]
     fn test_v3_output() {
-        let v3 = super::v3(b"barfoo", Some(wellknown::NS_X500));
+        let v3 = super::v3_with(b"barfoo", wellknown::NS_X500);
         assert_eq!(v3.to_string_hex(), "838ae739-5539-3a99-a67b-8e291e001842");
+
+        #[cfg(feature = "std")]
+        assert_eq!(super::v3(b"barfoo", Some(wellknown::NS_X500)), v3);
     }
 
     #[test]
     fn test_v5_output() {
-        let v5 = super::v5(b"foobar", Some(wellknown::NS_DNS));
+        let v5 = super::v5_with(b"foobar", wellknown::NS_DNS);
         assert_eq!(v5.to_string_hex(), "a050b517-6677-5119-9a77-2d26bbf30507");
+
+        #[cfg(feature = "std")]
+        assert_eq!(super::v5(b"foobar", Some(wellknown::NS_DNS)), v5);
+    }
+
+    #[test]
+    fn test_const_output() {
+        const V3: crate::Uuid = crate::const_v3!(wellknown::NS_X500, "barfoo");
+        const V5: crate::Uuid = crate::const_v5!(wellknown::NS_DNS, "foobar");
+
+        assert_eq!(V3.to_string_hex(), "838ae739-5539-3a99-a67b-8e291e001842");
+        assert_eq!(V5.to_string_hex(), "a050b517-6677-5119-9a77-2d26bbf30507");
+
+        let name = "a considerably longer name that spans more than a single block of the hash";
+        assert_eq!(
+            super::v3_const(name.as_bytes(), wellknown::NS_URL),
+            super::v3_with(name.as_bytes(), wellknown::NS_URL)
+        );
+        assert_eq!(
+            super::v5_const(name.as_bytes(), wellknown::NS_URL),
+            super::v5_with(name.as_bytes(), wellknown::NS_URL)
+        );
+    }
+
+    #[test]
+    fn test_const_macros_with_shadowed_str() {
+        #[allow(non_camel_case_types, dead_code)]
+        struct str;
+
+        const V5: crate::Uuid = crate::const_v5!(wellknown::NS_DNS, "foobar");
+        assert_eq!(V5.to_string_hex(), "a050b517-6677-5119-9a77-2d26bbf30507");
+    }
+
+    #[test]
+    fn test_v8_output() {
+        // Test vector from RFC 9562 Appendix B.2
+        let v8 = super::v8_sha256_with(b"www.example.com", wellknown::NS_DNS);
+        assert_eq!(v8.to_string_hex(), "5c146b14-3c52-8afd-938a-375d0df1fbf6");
+
+        #[cfg(feature = "std")]
+        assert_eq!(super::v8_sha256(b"www.example.com", Some(wellknown::NS_DNS)), v8);
+
+        // Not from the RFC, computed with Python's hashlib in the same way as above
+        let v8 = super::v8_sha512_with(b"www.example.com", wellknown::NS_DNS);
+        assert_eq!(v8.to_string_hex(), "94ee4ddb-9f36-8018-9ccf-86a4441691e0");
+
+        #[cfg(feature = "std")]
+        assert_eq!(super::v8_sha512(b"www.example.com", Some(wellknown::NS_DNS)), v8);
+    }
+
+    #[test]
+    fn test_builder_output() {
+        let mut v3 = NameBasedBuilder::v3(wellknown::NS_X500);
+        v3.update(b"bar");
+        v3.update(b"");
+        v3.update(b"foo");
+        assert_eq!(v3.finish().to_string_hex(), "838ae739-5539-3a99-a67b-8e291e001842");
+
+        let mut v5 = NameBasedBuilder::v5(wellknown::NS_DNS);
+        v5.update(b"foo");
+        v5.update(b"bar");
+        assert_eq!(v5.finish().to_string_hex(), "a050b517-6677-5119-9a77-2d26bbf30507");
+
+        let mut v8 = NameBasedBuilder::<sha2::Sha256>::v8(wellknown::NS_DNS);
+        v8.update(b"www.");
+        v8.update(b"example.com");
+        assert_eq!(v8.finish().to_string_hex(), "5c146b14-3c52-8afd-938a-375d0df1fbf6");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_builder_write() {
+        let mut v5 = NameBasedBuilder::v5(wellknown::NS_DNS);
+        std::io::copy(&mut &b"foobar"[..], &mut v5).unwrap();
+        assert_eq!(v5.finish().to_string_hex(), "a050b517-6677-5119-9a77-2d26bbf30507");
     }
 }
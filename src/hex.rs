@@ -0,0 +1,383 @@
+//! Hex decoding and encoding of the 32 digits of a UUID.
+//!
+//! On x86-64 the SSE2 implementation is always available, and the AVX2 one is
+//! selected at runtime when the `std` feature is enabled. On AArch64 the NEON
+//! implementation is used. Other targets use the table-driven scalar implementation,
+//! which the SIMD implementations are tested against.
+
+/// Marks bytes that are not hex digits in [`DECODE_TABLE`]
+const INVALID: u8 = 0xff;
+
+/// Value of every byte as a hex digit, accepting both upper and lower case
+static DECODE_TABLE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+
+    let mut i = 0;
+    while i < 10 {
+        table[b'0' as usize + i] = i as u8;
+        i += 1;
+    }
+
+    let mut i = 0;
+    while i < 6 {
+        table[b'a' as usize + i] = 10 + i as u8;
+        table[b'A' as usize + i] = 10 + i as u8;
+        i += 1;
+    }
+
+    table
+};
+
+static ENCODE_TABLE: [u8; 16] = *b"0123456789abcdef";
+
+/// Decodes 32 hex digits, returning `None` if any of them is not a hex digit
+#[inline]
+pub(crate) fn decode(digits: &[u8; 32]) -> Option<u128> {
+    #[cfg(all(target_arch = "x86_64", feature = "std"))]
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 support was detected
+        return unsafe { x86::decode_avx2(digits) };
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SAFETY: SSE2 is part of the x86-64 baseline
+        unsafe { x86::decode_sse2(digits) }
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        // SAFETY: NEON was enabled at compile time
+        unsafe { neon::decode(digits) }
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))))]
+    {
+        scalar::decode(digits)
+    }
+}
+
+/// Encodes a value as 32 lower case hex digits
+#[inline]
+pub(crate) fn encode(value: u128) -> [u8; 32] {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SAFETY: SSE2 is part of the x86-64 baseline
+        unsafe { x86::encode_sse2(value) }
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        // SAFETY: NEON was enabled at compile time
+        unsafe { neon::encode(value) }
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))))]
+    {
+        scalar::encode(value)
+    }
+}
+
+/// Encodes a value in the 8-4-4-4-12 format with lower case hex digits
+#[inline]
+pub(crate) fn encode_hyphenated(value: u128) -> [u8; 36] {
+    let digits = encode(value);
+    let mut output = [b'-'; 36];
+
+    output[0..8].copy_from_slice(&digits[0..8]);
+    output[9..13].copy_from_slice(&digits[8..12]);
+    output[14..18].copy_from_slice(&digits[12..16]);
+    output[19..23].copy_from_slice(&digits[16..20]);
+    output[24..36].copy_from_slice(&digits[20..32]);
+
+    output
+}
+
+#[cfg_attr(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon")), allow(dead_code))]
+mod scalar {
+    use super::{DECODE_TABLE, ENCODE_TABLE, INVALID};
+
+    pub(super) fn decode(digits: &[u8; 32]) -> Option<u128> {
+        let mut octets = [0u8; 16];
+
+        for (octet, pair) in octets.iter_mut().zip(digits.chunks_exact(2)) {
+            let hi = DECODE_TABLE[pair[0] as usize];
+            let lo = DECODE_TABLE[pair[1] as usize];
+
+            if hi == INVALID || lo == INVALID {
+                return None;
+            }
+
+            *octet = hi << 4 | lo;
+        }
+
+        Some(u128::from_be_bytes(octets))
+    }
+
+    pub(super) fn encode(value: u128) -> [u8; 32] {
+        let mut digits = [0u8; 32];
+
+        for (pair, octet) in digits.chunks_exact_mut(2).zip(value.to_be_bytes()) {
+            pair[0] = ENCODE_TABLE[(octet >> 4) as usize];
+            pair[1] = ENCODE_TABLE[(octet & 0xf) as usize];
+        }
+
+        digits
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    /// Converts 16 hex digits to their values, one per byte. The second value is the
+    /// mask of bytes that were valid hex digits
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn nibbles_sse2(chunk: __m128i) -> (__m128i, __m128i) {
+        // Bytes are compared as signed, so non-ASCII bytes wrap to negative values and
+        // fail both range checks
+        let digit = _mm_sub_epi8(chunk, _mm_set1_epi8(b'0' as i8));
+        let is_digit = _mm_and_si128(
+            _mm_cmpgt_epi8(digit, _mm_set1_epi8(-1)),
+            _mm_cmplt_epi8(digit, _mm_set1_epi8(10)),
+        );
+
+        let alpha = _mm_sub_epi8(_mm_or_si128(chunk, _mm_set1_epi8(0x20)), _mm_set1_epi8(b'a' as i8));
+        let is_alpha = _mm_and_si128(
+            _mm_cmpgt_epi8(alpha, _mm_set1_epi8(-1)),
+            _mm_cmplt_epi8(alpha, _mm_set1_epi8(6)),
+        );
+
+        let values = _mm_or_si128(
+            _mm_and_si128(digit, is_digit),
+            _mm_and_si128(_mm_add_epi8(alpha, _mm_set1_epi8(10)), is_alpha),
+        );
+
+        (values, _mm_or_si128(is_digit, is_alpha))
+    }
+
+    /// Joins pairs of digit values into octets, one per 16-bit lane
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn join_sse2(values: __m128i) -> __m128i {
+        // In little endian lanes the first digit of each pair is the low byte
+        let hi = _mm_slli_epi16(_mm_and_si128(values, _mm_set1_epi16(0x00ff)), 4);
+        let lo = _mm_srli_epi16(values, 8);
+
+        _mm_or_si128(hi, lo)
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn decode_sse2(digits: &[u8; 32]) -> Option<u128> {
+        let (first, first_valid) = nibbles_sse2(_mm_loadu_si128(digits.as_ptr() as *const __m128i));
+        let (second, second_valid) = nibbles_sse2(_mm_loadu_si128(digits.as_ptr().add(16) as *const __m128i));
+
+        if _mm_movemask_epi8(_mm_and_si128(first_valid, second_valid)) != 0xffff {
+            return None;
+        }
+
+        let octets = _mm_packus_epi16(join_sse2(first), join_sse2(second));
+
+        let mut output = [0u8; 16];
+        _mm_storeu_si128(output.as_mut_ptr() as *mut __m128i, octets);
+
+        Some(u128::from_be_bytes(output))
+    }
+
+    #[cfg(feature = "std")]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn decode_avx2(digits: &[u8; 32]) -> Option<u128> {
+        let chunk = _mm256_loadu_si256(digits.as_ptr() as *const __m256i);
+
+        // Same as nibbles_sse2, on all 32 digits at once
+        let digit = _mm256_sub_epi8(chunk, _mm256_set1_epi8(b'0' as i8));
+        let is_digit = _mm256_and_si256(
+            _mm256_cmpgt_epi8(digit, _mm256_set1_epi8(-1)),
+            _mm256_cmpgt_epi8(_mm256_set1_epi8(10), digit),
+        );
+
+        let alpha = _mm256_sub_epi8(_mm256_or_si256(chunk, _mm256_set1_epi8(0x20)), _mm256_set1_epi8(b'a' as i8));
+        let is_alpha = _mm256_and_si256(
+            _mm256_cmpgt_epi8(alpha, _mm256_set1_epi8(-1)),
+            _mm256_cmpgt_epi8(_mm256_set1_epi8(6), alpha),
+        );
+
+        if _mm256_movemask_epi8(_mm256_or_si256(is_digit, is_alpha)) != -1 {
+            return None;
+        }
+
+        let values = _mm256_or_si256(
+            _mm256_and_si256(digit, is_digit),
+            _mm256_and_si256(_mm256_add_epi8(alpha, _mm256_set1_epi8(10)), is_alpha),
+        );
+
+        let joined = _mm256_or_si256(
+            _mm256_slli_epi16(_mm256_and_si256(values, _mm256_set1_epi16(0x00ff)), 4),
+            _mm256_srli_epi16(values, 8),
+        );
+
+        // Packing works within 128-bit lanes, leaving the octets in the 1st and 3rd
+        // 64-bit elements
+        let packed = _mm256_packus_epi16(joined, _mm256_setzero_si256());
+        let octets = _mm256_castsi256_si128(_mm256_permute4x64_epi64(packed, 0b10_00));
+
+        let mut output = [0u8; 16];
+        _mm_storeu_si128(output.as_mut_ptr() as *mut __m128i, octets);
+
+        Some(u128::from_be_bytes(output))
+    }
+
+    /// Converts digit values to lower case ASCII
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn ascii_sse2(values: __m128i) -> __m128i {
+        // 'a' comes 39 characters after '0' + 10
+        let letter_offset = _mm_and_si128(_mm_cmpgt_epi8(values, _mm_set1_epi8(9)), _mm_set1_epi8(39));
+
+        _mm_add_epi8(_mm_add_epi8(values, _mm_set1_epi8(b'0' as i8)), letter_offset)
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn encode_sse2(value: u128) -> [u8; 32] {
+        let octets = value.to_be_bytes();
+        let chunk = _mm_loadu_si128(octets.as_ptr() as *const __m128i);
+
+        let mask = _mm_set1_epi8(0x0f);
+        let hi = _mm_and_si128(_mm_srli_epi16(chunk, 4), mask);
+        let lo = _mm_and_si128(chunk, mask);
+
+        let mut output = [0u8; 32];
+        _mm_storeu_si128(output.as_mut_ptr() as *mut __m128i, ascii_sse2(_mm_unpacklo_epi8(hi, lo)));
+        _mm_storeu_si128(output.as_mut_ptr().add(16) as *mut __m128i, ascii_sse2(_mm_unpackhi_epi8(hi, lo)));
+
+        output
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon {
+    use core::arch::aarch64::*;
+
+    /// Converts 16 hex digits to their values, one per byte. The second value has all
+    /// bits set for bytes that were valid hex digits
+    #[inline]
+    #[target_feature(enable = "neon")]
+    unsafe fn nibbles(chunk: uint8x16_t) -> (uint8x16_t, uint8x16_t) {
+        let digit = vsubq_u8(chunk, vdupq_n_u8(b'0'));
+        let is_digit = vcltq_u8(digit, vdupq_n_u8(10));
+
+        let alpha = vsubq_u8(vorrq_u8(chunk, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+        let is_alpha = vcltq_u8(alpha, vdupq_n_u8(6));
+
+        let values = vorrq_u8(
+            vandq_u8(digit, is_digit),
+            vandq_u8(vaddq_u8(alpha, vdupq_n_u8(10)), is_alpha),
+        );
+
+        (values, vorrq_u8(is_digit, is_alpha))
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn decode(digits: &[u8; 32]) -> Option<u128> {
+        let (first, first_valid) = nibbles(vld1q_u8(digits.as_ptr()));
+        let (second, second_valid) = nibbles(vld1q_u8(digits.as_ptr().add(16)));
+
+        if vminvq_u8(vandq_u8(first_valid, second_valid)) != 0xff {
+            return None;
+        }
+
+        // The even digits are the high nibbles of the octets
+        let hi = vuzp1q_u8(first, second);
+        let lo = vuzp2q_u8(first, second);
+
+        let mut output = [0u8; 16];
+        vst1q_u8(output.as_mut_ptr(), vorrq_u8(vshlq_n_u8(hi, 4), lo));
+
+        Some(u128::from_be_bytes(output))
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn encode(value: u128) -> [u8; 32] {
+        let octets = value.to_be_bytes();
+        let chunk = vld1q_u8(octets.as_ptr());
+        let table = vld1q_u8(super::ENCODE_TABLE.as_ptr());
+
+        let hi = vshrq_n_u8(chunk, 4);
+        let lo = vandq_u8(chunk, vdupq_n_u8(0x0f));
+
+        let mut output = [0u8; 32];
+        vst1q_u8(output.as_mut_ptr(), vqtbl1q_u8(table, vzip1q_u8(hi, lo)));
+        vst1q_u8(output.as_mut_ptr().add(16), vqtbl1q_u8(table, vzip2q_u8(hi, lo)));
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inputs with every byte value at every position, around valid digits
+    fn inputs() -> impl Iterator<Item = [u8; 32]> {
+        let bases = [*b"0123456789abcdefABCDEF0123456789", *b"ffffffffffffffffffffffffffffffff"];
+
+        bases.into_iter().flat_map(|base| {
+            (0..32).flat_map(move |position| {
+                (0..=255u8).map(move |byte| {
+                    let mut input = base;
+                    input[position] = byte;
+                    input
+                })
+            })
+        })
+    }
+
+    /// Checks an implementation against the scalar one
+    fn check_decode(decode: impl Fn(&[u8; 32]) -> Option<u128>) {
+        for input in inputs() {
+            assert_eq!(decode(&input), scalar::decode(&input), "{input:?}");
+        }
+    }
+
+    fn check_encode(encode: impl Fn(u128) -> [u8; 32]) {
+        for input in inputs().filter_map(|input| scalar::decode(&input)) {
+            assert_eq!(encode(input), scalar::encode(input), "{input:x}");
+        }
+
+        for value in [0, u128::MAX, 0x0123456789abcdef0123456789abcdef] {
+            assert_eq!(encode(value), scalar::encode(value));
+        }
+    }
+
+    #[test]
+    fn test_scalar() {
+        assert_eq!(scalar::decode(b"0123456789abcdefABCDEF0123456789"), Some(0x0123456789abcdefabcdef0123456789));
+        assert_eq!(scalar::decode(b"0123456789abcdefABCDEF012345678g"), None);
+        assert_eq!(&scalar::encode(0x0123456789abcdefabcdef0123456789), b"0123456789abcdefabcdef0123456789");
+
+        for value in [0, u128::MAX, 0x0123456789abcdefabcdef0123456789] {
+            assert_eq!(scalar::decode(&scalar::encode(value)), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        check_decode(decode);
+        check_encode(encode);
+
+        assert_eq!(&encode_hyphenated(0x0123456789abcdefabcdef0123456789), b"01234567-89ab-cdef-abcd-ef0123456789");
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_x86() {
+        check_decode(|digits| unsafe { x86::decode_sse2(digits) });
+        check_encode(|value| unsafe { x86::encode_sse2(value) });
+
+        #[cfg(feature = "std")]
+        if std::is_x86_feature_detected!("avx2") {
+            check_decode(|digits| unsafe { x86::decode_avx2(digits) });
+        }
+    }
+}
@@ -7,6 +7,10 @@ use num_traits::cast::ToPrimitive;
 
 use crate::uuid::Uuid;
 
+mod validate;
+
+pub use validate::{ensure_valid, validate, Finding, InvalidUuid, Policy, Rule, Severity};
+
 /// The individual fields of a UUID as per RFC 4122. Each field is stored in
 /// big-endian order
 #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen::prelude::wasm_bindgen)]
@@ -25,7 +29,7 @@ impl UuidFields {
         let octets = uuid.value().to_be_bytes();
 
         // Fields of the UUID as per RFC section 4.1.2
-        let time_low = u32::from_be_bytes(octets[00..=03].try_into().unwrap());
+        let time_low = u32::from_be_bytes(octets[0..=3].try_into().unwrap());
         let time_mid = ((octets[4] as u16) << 8) | octets[5] as u16;
         let time_hi_and_version = ((octets[6] as u16) << 8) | octets[7] as u16;
         let clk_seq_hi_res = octets[8];
@@ -46,22 +50,40 @@ impl UuidFields {
             node,
         }
     }
+
+    /// Reassembles the UUID, the inverse of [`UuidFields::of`]
+    pub(crate) fn uuid(&self) -> Uuid {
+        let mut octets = [0; 16];
+
+        octets[0..=3].copy_from_slice(&self.time_low.to_be_bytes());
+        octets[4..=5].copy_from_slice(&self.time_mid.to_be_bytes());
+        octets[6..=7].copy_from_slice(&self.time_hi_and_version.to_be_bytes());
+        octets[8] = self.clk_seq_hi_res;
+        octets[9] = self.clk_seq_low;
+        octets[10..=15].copy_from_slice(&self.node.to_be_bytes()[2..8]);
+
+        Uuid::from_value(u128::from_be_bytes(octets))
+    }
 }
 
 #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen::prelude::wasm_bindgen)]
 #[derive(Clone, Debug)]
 pub struct UuidDetails {
-    /// The 60-bit field of the UUID. This is the number of 100-nanosecond intervals
-    /// since 00:00:00.00, 15 October 1582
+    /// The 60-bit time field of the UUID, read with the layout of V1 UUIDs. For V1
+    /// UUIDs this is the number of 100-nanosecond intervals since 00:00:00.00,
+    /// 15 October 1582. Other versions store time differently or not at all, so
+    /// prefer [`UuidDetails::timestamp`] which takes the version into account
     pub time: u64,
 
-    /// The 4-bit version field of the UUID
+    /// The 4-bit version field of the UUID. This is 0 for the Nil and Max UUIDs, which
+    /// have no version (see [`UuidDetails::special`])
     pub version: u8,
 
     /// The 8-bit variant field of the UUID.
     ///
     /// UUID variant is encoded in a variable number of bits. For this reason this contains
-    /// the full octet containing the variant, but with all the clock sequence bits set to 0
+    /// the full octet containing the variant, but with all the clock sequence bits set to 0.
+    /// This is 0 for the Nil and Max UUIDs, which have no variant
     pub variant: u8,
 
     /// The 14-bit clock sequence of the UUID
@@ -69,9 +91,70 @@ pub struct UuidDetails {
 
     /// The 48-bit node field of the UUID
     pub node: u64,
+
+    timestamp: Option<Timestamp>,
+
+    special: Option<Special>,
+}
+
+/// The special UUIDs of RFC 9562, whose bits are not laid out in fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Special {
+    /// All bits set to zero (RFC 9562 Section 5.9)
+    Nil,
+
+    /// All bits set to one (RFC 9562 Section 5.10)
+    Max,
+}
+
+/// The timestamp stored in a time based UUID, as returned by [`UuidDetails::timestamp`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timestamp {
+    /// Number of 100-nanosecond intervals since 00:00:00.00, 15 October 1582, as stored
+    /// by V1, V2 and V6 UUIDs
+    Gregorian(u64),
+
+    /// Number of milliseconds since 00:00:00.00, 01 January 1970, as stored by V7 UUIDs
+    UnixMillis(u64),
+}
+
+impl Timestamp {
+    /// Returns the timestamp relative to the Unix epoch. See [`TimeSpec`] for more
+    /// details about the return type
+    pub fn unix_time(&self) -> TimeSpec {
+        match *self {
+            Timestamp::Gregorian(ticks) => {
+                // The offset in 100-nanosecond intervals
+                let offset = crate::constants::MILLISECS_GREGORIAN_UNIX as i128 * 10000;
+
+                // Computed in 128 bits, since a timestamp built by the caller may not fit
+                // in the 60 bits of a UUID
+                let mut time = ConsumingI128(ticks as i128 - offset);
+
+                let mut timespec = TimeSpec::zero();
+
+                timespec.nanoseconds = time.divn_mod(10) * 100;
+                timespec.microseconds = time.divn_mod(1000000);
+                timespec.seconds = time.remaining();
+
+                timespec
+            }
+
+            Timestamp::UnixMillis(millis) => {
+                let mut time = ConsumingI128(millis as i128);
+
+                let mut timespec = TimeSpec::zero();
+
+                timespec.microseconds = time.divn_mod(1000) * 1000;
+                timespec.seconds = time.remaining();
+
+                timespec
+            }
+        }
+    }
 }
 
-/// The time information returned by `UuidFields::unix_time()` method
+/// The time information returned by `UuidDetails::unix_time()` method
 ///
 /// UUIDs contain a timestamp with resolution upto nanoseconds (read below), which sometimes doesn't fit
 /// in a single 64-bit integer. This struct contains the timestamp in a destructed way in multiple
@@ -81,15 +164,24 @@ pub struct UuidDetails {
 /// after one taken into account `microseconds`.
 ///
 /// As an example, the full timestamp as a single number in nanoseconds is given by
-/// `seconds * 1_000_000_000 + microseconds * 1000 + nanoseconds`
+/// `seconds * 1_000_000_000 + microseconds * 1000 + nanoseconds` (see [`TimeSpec::as_nanos`])
+///
+/// `seconds` is negative for timestamps before 00:00:00.00, 01 January 1970, which
+/// UUIDs can represent back to 15 October 1582. `microseconds` and `nanoseconds` are
+/// never negative, so 100 nanoseconds before the Unix epoch is represented as
+/// `seconds = -1`, `microseconds = 999999` and `nanoseconds = 900`.
 ///
 /// UUIDs store time as count of 100-nanoseconds intervals. So the maximun resolution available is
 /// 100-nanoseconds. As a result, `nanoseconds` will always be a multiple of 100
+///
+/// A `TimeSpec` can be converted to [`core::time::Duration`] (since the Unix epoch),
+/// `std::time::SystemTime`, and with the `chrono` and `time` features to
+/// `chrono::DateTime<Utc>` and `time::OffsetDateTime`, using `TryFrom`.
 #[cfg_attr(feature = "wasm-bindgen", wasm_bindgen::prelude::wasm_bindgen)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TimeSpec {
     /// Seconds of the timestamp
-    pub seconds: u64,
+    pub seconds: i64,
 
     /// Microseconds of the timestamp
     pub microseconds: i32,
@@ -106,14 +198,90 @@ impl TimeSpec {
             nanoseconds: 0
         }
     }
+
+    /// Returns the number of nanoseconds since the Unix epoch
+    pub fn as_nanos(&self) -> i128 {
+        self.seconds as i128 * 1_000_000_000
+            + self.microseconds as i128 * 1000
+            + self.nanoseconds as i128
+    }
+
+    /// Returns the sub-second part of the timestamp in nanoseconds
+    fn subsec_nanos(&self) -> u32 {
+        (self.microseconds * 1000 + self.nanoseconds) as u32
+    }
+}
+
+/// Error returned when a [`TimeSpec`] does not fit in the range of the target time type
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Timestamp is out of range of the target type")]
+pub struct TimeRangeError;
+
+impl TryFrom<TimeSpec> for core::time::Duration {
+    type Error = TimeRangeError;
+
+    /// Converts to the duration since the Unix epoch. Fails for earlier timestamps
+    fn try_from(value: TimeSpec) -> Result<Self, Self::Error> {
+        let seconds = u64::try_from(value.seconds).map_err(|_| TimeRangeError)?;
+        Ok(core::time::Duration::new(seconds, value.subsec_nanos()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<TimeSpec> for std::time::SystemTime {
+    type Error = TimeRangeError;
+
+    fn try_from(value: TimeSpec) -> Result<Self, Self::Error> {
+        use core::time::Duration;
+        use std::time::UNIX_EPOCH;
+
+        if value.seconds >= 0 {
+            UNIX_EPOCH.checked_add(Duration::try_from(value)?)
+        } else {
+            UNIX_EPOCH
+                .checked_sub(Duration::from_secs(value.seconds.unsigned_abs()))
+                .and_then(|time| time.checked_add(Duration::new(0, value.subsec_nanos())))
+        }
+        .ok_or(TimeRangeError)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<TimeSpec> for chrono::DateTime<chrono::Utc> {
+    type Error = TimeRangeError;
+
+    fn try_from(value: TimeSpec) -> Result<Self, Self::Error> {
+        chrono::DateTime::from_timestamp(value.seconds, value.subsec_nanos()).ok_or(TimeRangeError)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<TimeSpec> for time::OffsetDateTime {
+    type Error = TimeRangeError;
+
+    fn try_from(value: TimeSpec) -> Result<Self, Self::Error> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(value.as_nanos()).map_err(|_| TimeRangeError)
+    }
 }
 
 impl UuidDetails {
     pub fn construct(fields: &UuidFields) -> Self {
+        Self::of(&fields.uuid(), fields)
+    }
+
+    /// Decodes `uuid`, whose fields are given in `fields`
+    pub(crate) fn of(uuid: &Uuid, fields: &UuidFields) -> Self {
+        let special = if uuid.is_nil() {
+            Some(Special::Nil)
+        } else if uuid.is_max() {
+            Some(Special::Max)
+        } else {
+            None
+        };
 
         let version = ((fields.time_hi_and_version & 0xf000) >> 12) as u8;
 
-        let time_epoch_millisecs = {
+        let v1_time = {
             // Remove the version bits from timestamp
             let time_hi_and_version = (fields.time_hi_and_version & 0x0fff) as u64;
             let time_mid = fields.time_mid as u64;
@@ -121,9 +289,7 @@ impl UuidDetails {
 
             // Combine the 3 fields into full timestamp. This will represent the
             // count of 100-nanosecond intervals since 00:00:00.00, 15 October 1582
-            let uuidtime = time_hi_and_version << 48 | time_mid << 32 | time_low;
-
-            uuidtime
+            time_hi_and_version << 48 | time_mid << 32 | time_low
         };
 
         // The clk_seq_hi_res field contains both the variant the high byte of clock
@@ -157,48 +323,101 @@ impl UuidDetails {
 
         let clock_seq = ((clk_seq_hi as u16) << 8) | fields.clk_seq_low as u16;
 
+        // Version numbers are only defined for the RFC 4122 variant
+        let timestamp = match version {
+            _ if variant != 0x80 => None,
+
+            1 => Some(Timestamp::Gregorian(v1_time)),
+
+            // DCE Security UUIDs replace the time_low field with a local identifier,
+            // leaving only the most significant 28 bits of the timestamp
+            2 => Some(Timestamp::Gregorian(v1_time & !0xffff_ffff)),
+
+            // The same timestamp as V1, with the most significant bits first
+            6 => {
+                let time_high = fields.time_low as u64;
+                let time_mid = fields.time_mid as u64;
+                let time_low = (fields.time_hi_and_version & 0x0fff) as u64;
+
+                Some(Timestamp::Gregorian(time_high << 28 | time_mid << 12 | time_low))
+            }
+
+            // 48-bit Unix timestamp in milliseconds
+            7 => {
+                let unix_ts_ms = (fields.time_low as u64) << 16 | fields.time_mid as u64;
+                Some(Timestamp::UnixMillis(unix_ts_ms))
+            }
+
+            _ => None,
+        };
+
+        // The Nil and Max UUIDs have no version and variant, which would otherwise
+        // be read as version 15 and a reserved variant for the Max UUID
+        let (version, variant) = match special {
+            Some(_) => (0, 0),
+            None => (version, variant),
+        };
+
         Self {
-            time: time_epoch_millisecs,
+            time: v1_time,
             version,
             variant,
             clock_seq,
             node: fields.node,
+            timestamp,
+            special,
         }
     }
 
-    /// Returns the timestamp of the UUID. See [`TimeSpec`] for more details about
-    /// the return type 
-    pub fn unix_time(&self) -> TimeSpec {
-        // The offset in 100-nanosecond intervals
-        let offset = crate::constants::MILLISECS_GREGORIAN_UNIX * 10000;
+    /// Returns which special UUID this is, if any
+    pub fn special(&self) -> Option<Special> {
+        self.special
+    }
 
-        let mut time = ConsumingU64(self.time.saturating_sub(offset));
+    /// Returns the timestamp of the UUID, read according to its version. Returns
+    /// `None` for UUIDs that do not carry a timestamp, i.e. versions other than 1, 2,
+    /// 6 and 7, or a variant other than the one specified in RFC 4122
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        self.timestamp
+    }
 
-        let mut timespec = TimeSpec::zero();
+    /// Returns the 48-bit millisecond timestamp at the start of a ULID, regardless of
+    /// the version and variant bits. For V7 UUIDs this is the same as
+    /// [`UuidDetails::timestamp`], since V7 UUIDs share the layout of ULIDs
+    pub fn ulid_timestamp(&self) -> Timestamp {
+        // The first 48 bits are the time_low and time_mid fields
+        let time_low = self.time & 0xffff_ffff;
+        let time_mid = (self.time >> 32) & 0xffff;
 
-        timespec.nanoseconds = time.divn_mod(10) * 100;
-        timespec.microseconds = time.divn_mod(1000000);
-        timespec.seconds = time.remaining();
+        Timestamp::UnixMillis(time_low << 16 | time_mid)
+    }
 
-        timespec
+    /// Returns the timestamp of the UUID relative to the Unix epoch, or `None` if the
+    /// UUID does not carry a timestamp. See [`TimeSpec`] for more details about the
+    /// return type
+    pub fn unix_time(&self) -> Option<TimeSpec> {
+        self.timestamp.map(|timestamp| timestamp.unix_time())
     }
 }
 
-struct ConsumingU64(u64);
+struct ConsumingI128(i128);
 
-impl ConsumingU64 {
+impl ConsumingI128 {
+    // Uses floored division, so that the remainder is never negative
     fn divn_mod<T>(&mut self, n: T) -> T
     where
         T: Num + ToPrimitive + FromPrimitive,
     {
-        let n = n.to_u64().unwrap();
-        let rem = self.0 % n;
-        self.0 /= n;
-        T::from_u64(rem).unwrap()
+        let n = n.to_i128().unwrap();
+        let rem = self.0.rem_euclid(n);
+        self.0 = self.0.div_euclid(n);
+        T::from_i128(rem).unwrap()
     }
 
-    fn remaining(self) -> u64 {
-        self.0
+    // The whole seconds of a 64-bit count of milliseconds or 100-nanosecond
+    // intervals always fit in 64 bits
+    fn remaining(self) -> i64 {
+        self.0 as i64
     }
 }
 
@@ -237,10 +456,166 @@ mod tests {
         let uuid = Uuid::parse("fe4d0d06-adf3-1fff-bdd3-325096b39f47").unwrap();
         let d = UuidDetails::construct(&UuidFields::of(&uuid));
 
-        let time = d.unix_time();
+        let time = d.unix_time().unwrap();
 
         assert_eq!(time.seconds, 103063836508);
         assert_eq!(time.microseconds, 525696);
         assert_eq!(time.nanoseconds, 600);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_unix_time_before_epoch() {
+        // 100 nanoseconds before the Unix epoch
+        let uuid = Uuid::parse("13813fff-1dd2-11b2-8000-000000000000").unwrap();
+        let time = uuid.details().unix_time().unwrap();
+
+        assert_eq!(time.seconds, -1);
+        assert_eq!(time.microseconds, 999999);
+        assert_eq!(time.nanoseconds, 900);
+        assert_eq!(time.as_nanos(), -100);
+
+        // The Gregorian epoch
+        let time = Timestamp::Gregorian(0).unix_time();
+        assert_eq!(time.seconds, -12219292800);
+        assert_eq!(time.as_nanos(), -12219292800 * 1_000_000_000);
+    }
+
+    #[test]
+    fn test_unix_time_out_of_range() {
+        // Larger than any timestamp stored in a UUID, but still valid values
+        let time = Timestamp::Gregorian(u64::MAX).unix_time();
+        assert_eq!(time.seconds, 1832455114570);
+        assert_eq!(time.microseconds, 955161);
+        assert_eq!(time.nanoseconds, 500);
+        assert_eq!(time.as_nanos(), (u64::MAX as i128 - 122192928000000000) * 100);
+
+        let time = Timestamp::UnixMillis(u64::MAX).unix_time();
+        assert_eq!(time.seconds, 18446744073709551);
+        assert_eq!(time.microseconds, 615000);
+        assert_eq!(time.nanoseconds, 0);
+        assert_eq!(time.as_nanos(), u64::MAX as i128 * 1_000_000);
+    }
+
+    #[test]
+    fn test_time_conversions() {
+        use core::time::Duration;
+
+        let uuid = Uuid::parse("e47e7da8-adf1-11ee-b053-325096b39f47").unwrap();
+        let time = uuid.details().unix_time().unwrap();
+
+        let duration = Duration::try_from(time).unwrap();
+        assert_eq!(duration.as_nanos() as i128, time.as_nanos());
+
+        let before_epoch = Timestamp::Gregorian(0).unix_time();
+        assert_eq!(Duration::try_from(before_epoch), Err(TimeRangeError));
+
+        #[cfg(feature = "std")]
+        {
+            use std::time::{SystemTime, UNIX_EPOCH};
+
+            let system_time = SystemTime::try_from(time).unwrap();
+            assert_eq!(system_time.duration_since(UNIX_EPOCH).unwrap(), duration);
+
+            let system_time = SystemTime::try_from(before_epoch).unwrap();
+            assert_eq!(
+                UNIX_EPOCH.duration_since(system_time).unwrap(),
+                Duration::from_secs(12219292800)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_chrono_conversion() {
+        use chrono::{DateTime, NaiveDate, Utc};
+
+        let uuid = Uuid::parse("e47e7da8-adf1-11ee-b053-325096b39f47").unwrap();
+        let time = DateTime::<Utc>::try_from(uuid.details().unix_time().unwrap()).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap().and_hms_nano_opt(6, 48, 12, 728260000);
+        assert_eq!(Some(time.naive_utc()), expected);
+
+        let time = DateTime::<Utc>::try_from(Timestamp::Gregorian(0).unix_time()).unwrap();
+        let expected = NaiveDate::from_ymd_opt(1582, 10, 15).unwrap().and_hms_opt(0, 0, 0);
+        assert_eq!(Some(time.naive_utc()), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_time_crate_conversion() {
+        use time::{Month, OffsetDateTime};
+
+        let uuid = Uuid::parse("e47e7da8-adf1-11ee-b053-325096b39f47").unwrap();
+        let time = OffsetDateTime::try_from(uuid.details().unix_time().unwrap()).unwrap();
+        assert_eq!((time.year(), time.month(), time.day()), (2024, Month::January, 8));
+        assert_eq!(time.nanosecond(), 728260000);
+
+        let time = OffsetDateTime::try_from(Timestamp::Gregorian(0).unix_time()).unwrap();
+        assert_eq!((time.year(), time.month(), time.day()), (1582, Month::October, 15));
+    }
+
+    #[test]
+    fn test_timestamp_versions() {
+        // V1 and V6 UUIDs with the same contents
+        let v1 = Uuid::parse("d71c7cd2-aa3b-11ee-ac4a-325096b39f47").unwrap().details();
+        let v6 = Uuid::parse("1eeaa3bd-71c7-6cd2-ac4a-325096b39f47").unwrap().details();
+
+        assert_eq!(v1.timestamp(), Some(Timestamp::Gregorian(0x1ee_aa3b_d71c_7cd2)));
+        assert_eq!(v6.timestamp(), v1.timestamp());
+        assert_eq!(v6.clock_seq, v1.clock_seq);
+        assert_eq!(v6.node, v1.node);
+
+        // Example from RFC 9562 Appendix A.6
+        let v7 = Uuid::parse("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap().details();
+        assert_eq!(v7.timestamp(), Some(Timestamp::UnixMillis(0x017f_22e2_79b0)));
+
+        let time = v7.unix_time().unwrap();
+        assert_eq!(time.seconds, 1645557742);
+        assert_eq!(time.microseconds, 0);
+        assert_eq!(time.nanoseconds, 0);
+
+        assert_eq!(v7.ulid_timestamp(), v7.timestamp().unwrap());
+
+        // ULIDs carry the timestamp without the version bits
+        let ulid = Uuid::parse("01563df3-6481-d676-4c61-efb99302bd5b").unwrap().details();
+        assert_eq!(ulid.timestamp(), None);
+        assert_eq!(ulid.ulid_timestamp(), Timestamp::UnixMillis(1469918176385));
+
+        // DCE Security UUIDs keep only the high bits of the timestamp
+        let v2 = Uuid::parse("000003e8-aa3b-21ee-ac4a-325096b39f47").unwrap().details();
+        assert_eq!(v2.timestamp(), Some(Timestamp::Gregorian(0x1ee_aa3b_0000_0000)));
+
+        // Versions without a timestamp
+        let v4 = Uuid::parse("ffb82219-2be8-4961-8c83-2163e1b4b966").unwrap().details();
+        assert_eq!(v4.timestamp(), None);
+        assert_eq!(v4.unix_time(), None);
+
+        let v5 = Uuid::parse("a050b517-6677-5119-9a77-2d26bbf30507").unwrap().details();
+        assert_eq!(v5.timestamp(), None);
+
+        // The version bits of other variants do not mean the same thing
+        let microsoft = Uuid::parse("d71c7cd2-aa3b-11ee-cc4a-325096b39f47").unwrap().details();
+        assert_eq!(microsoft.timestamp(), None);
+    }
+
+    #[test]
+    fn test_special() {
+        let nil = crate::wellknown::Nil.details();
+        assert_eq!(nil.special(), Some(Special::Nil));
+        assert_eq!((nil.version, nil.variant), (0, 0));
+        assert_eq!(nil.timestamp(), None);
+
+        let max = crate::wellknown::MAX.details();
+        assert_eq!(max.special(), Some(Special::Max));
+        assert_eq!((max.version, max.variant), (0, 0));
+        assert_eq!(max.timestamp(), None);
+
+        let almost_max = Uuid::from_value(u128::MAX - 1).details();
+        assert_eq!(almost_max.special(), None);
+        assert_eq!((almost_max.version, almost_max.variant), (15, 0xE0));
+
+        // Details built from the fields alone detect the special UUIDs too
+        let fields = UuidFields::of(&crate::wellknown::MAX);
+        assert_eq!(fields.uuid(), crate::wellknown::MAX);
+        assert_eq!(UuidDetails::construct(&fields).special(), Some(Special::Max));
+    }
+}
@@ -0,0 +1,353 @@
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use super::Timestamp;
+use crate::uuid::Uuid;
+
+/// How serious a [`Finding`] is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// The rules checked by [`validate`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// The variant bits are not the ones specified in RFC 9562
+    NonRfcVariant,
+
+    /// The version is not one of the versions defined in RFC 9562 (1 to 8)
+    UnknownVersion,
+
+    /// The random bits of a V4 UUID look far from random
+    LowEntropy,
+
+    /// The timestamp of a V1, V2, V6 or V7 UUID lies in the future
+    FutureTimestamp,
+
+    /// The timestamp of a V7 UUID is earlier than [`Policy::min_v7_unix_millis`]
+    EarlyTimestamp,
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let message = match self {
+            Rule::NonRfcVariant => "variant is not the one specified in RFC 9562",
+            Rule::UnknownVersion => "version is not defined in RFC 9562",
+            Rule::LowEntropy => "random bits have suspiciously low entropy",
+            Rule::FutureTimestamp => "timestamp lies in the future",
+            Rule::EarlyTimestamp => "timestamp is earlier than allowed",
+        };
+
+        write!(f, "{message}")
+    }
+}
+
+/// A rule violated by a UUID, as reported by [`validate`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Finding {
+    pub rule: Rule,
+    pub severity: Severity,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.rule)
+    }
+}
+
+/// Configures which rules [`validate`] checks, and how serious each violation is.
+/// A severity of `None` disables the rule.
+#[derive(Clone, Debug)]
+pub struct Policy {
+    pub non_rfc_variant: Option<Severity>,
+    pub unknown_version: Option<Severity>,
+    pub low_entropy: Option<Severity>,
+    pub future_timestamp: Option<Severity>,
+    pub early_timestamp: Option<Severity>,
+
+    /// The current Unix time in milliseconds, used to detect future timestamps. If
+    /// `None`, the system clock is read when the `std` feature is enabled, otherwise
+    /// the future timestamp rule is skipped
+    pub now_unix_millis: Option<u64>,
+
+    /// How far in the future a timestamp may lie before it is reported, to allow for
+    /// clock skew between machines
+    pub max_clock_skew_millis: u64,
+
+    /// The earliest Unix time in milliseconds accepted in V7 UUIDs
+    pub min_v7_unix_millis: u64,
+}
+
+impl Default for Policy {
+    /// Reports malformed UUIDs as errors, and implausible ones as warnings
+    fn default() -> Self {
+        Self {
+            non_rfc_variant: Some(Severity::Error),
+            unknown_version: Some(Severity::Error),
+            low_entropy: Some(Severity::Warning),
+            future_timestamp: Some(Severity::Warning),
+            early_timestamp: Some(Severity::Warning),
+            now_unix_millis: None,
+            max_clock_skew_millis: 60_000,
+
+            // 00:00:00.00, 01 January 2000
+            min_v7_unix_millis: 946_684_800_000,
+        }
+    }
+}
+
+impl Policy {
+    /// A policy that reports every violation as an error, meant to be used with
+    /// [`ensure_valid`] at API boundaries
+    pub fn strict() -> Self {
+        Self {
+            non_rfc_variant: Some(Severity::Error),
+            unknown_version: Some(Severity::Error),
+            low_entropy: Some(Severity::Error),
+            future_timestamp: Some(Severity::Error),
+            early_timestamp: Some(Severity::Error),
+            ..Self::default()
+        }
+    }
+
+    fn now_unix_millis(&self) -> Option<u64> {
+        #[cfg(feature = "std")]
+        {
+            use crate::time_based::{Clock, SystemClock};
+            Some(self.now_unix_millis.unwrap_or_else(|| SystemClock.unix_millis()))
+        }
+
+        #[cfg(not(feature = "std"))]
+        self.now_unix_millis
+    }
+}
+
+/// Error returned by [`ensure_valid`], containing the findings of [`Severity::Error`]
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[error("UUID violates {} rule(s), first: {}", .findings.len(), .findings[0])]
+pub struct InvalidUuid {
+    findings: Vec<Finding>,
+}
+
+impl InvalidUuid {
+    /// The findings of [`Severity::Error`], of which there is at least one
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+}
+
+/// Checks a UUID against the rules enabled in `policy` and returns the violated
+/// ones. An empty result means the UUID is valid
+///
+/// ```
+/// use uuidland::Uuid;
+/// use uuidland::inspect::{validate, Policy, Rule};
+///
+/// // A V1 UUID with the Microsoft variant
+/// let uuid = Uuid::parse("d71c7cd2-aa3b-11ee-cc4a-325096b39f47").unwrap();
+/// let findings = validate(&uuid, &Policy::default());
+///
+/// assert_eq!(findings.len(), 1);
+/// assert_eq!(findings[0].rule, Rule::NonRfcVariant);
+/// ```
+pub fn validate(uuid: &Uuid, policy: &Policy) -> Vec<Finding> {
+    let details = uuid.details();
+    let mut findings = Vec::new();
+
+    let mut report = |rule: Rule, severity: Option<Severity>| {
+        if let Some(severity) = severity {
+            findings.push(Finding { rule, severity });
+        }
+    };
+
+    // The Nil and Max UUIDs are valid, even though they have no version and variant
+    if details.special().is_some() {
+        return findings;
+    }
+
+    // The version is meaningless for other variants, so nothing else can be checked
+    if details.variant != 0x80 {
+        report(Rule::NonRfcVariant, policy.non_rfc_variant);
+        return findings;
+    }
+
+    if !(1..=8).contains(&details.version) {
+        report(Rule::UnknownVersion, policy.unknown_version);
+    }
+
+    if details.version == 4 && has_low_entropy(uuid) {
+        report(Rule::LowEntropy, policy.low_entropy);
+    }
+
+    if let Some(timestamp) = details.timestamp() {
+        let unix_nanos = timestamp.unix_time().as_nanos();
+
+        if let Some(now) = policy.now_unix_millis() {
+            let latest = now.saturating_add(policy.max_clock_skew_millis) as i128 * 1_000_000;
+
+            if unix_nanos > latest {
+                report(Rule::FutureTimestamp, policy.future_timestamp);
+            }
+        }
+
+        if let Timestamp::UnixMillis(millis) = timestamp {
+            if millis < policy.min_v7_unix_millis {
+                report(Rule::EarlyTimestamp, policy.early_timestamp);
+            }
+        }
+    }
+
+    findings
+}
+
+/// Checks a UUID against `policy` like [`validate`], but fails if any finding has
+/// [`Severity::Error`]. Use with [`Policy::strict`] to reject every violation
+pub fn ensure_valid(uuid: &Uuid, policy: &Policy) -> Result<(), InvalidUuid> {
+    let findings: Vec<Finding> = validate(uuid, policy)
+        .into_iter()
+        .filter(|finding| finding.severity == Severity::Error)
+        .collect();
+
+    if findings.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidUuid { findings })
+    }
+}
+
+/// Tells whether the 122 random bits of a V4 UUID are implausible as the output of a
+/// random number generator. The number of set bits must be within about 5.5 standard
+/// deviations of the expected 61, which random bits fail with a probability below
+/// 2^-25, and no run of equal bits may be longer than 32 bits, which they fail with a
+/// probability below 2^-26. Together, about one in 37 million random UUIDs is flagged
+fn has_low_entropy(uuid: &Uuid) -> bool {
+    let random = uuid.payload();
+
+    let ones = random.count_ones();
+    if !(31..=91).contains(&ones) {
+        return true;
+    }
+
+    let mut longest_run = 0;
+    let mut run = 0;
+    let mut last_bit = None;
+
+    for i in 0..122 {
+        let bit = (random >> i) & 1;
+
+        if Some(bit) == last_bit {
+            run += 1;
+        } else {
+            run = 1;
+            last_bit = Some(bit);
+        }
+
+        longest_run = longest_run.max(run);
+    }
+
+    longest_run > 32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn rules(uuid: &str, policy: &Policy) -> Vec<Rule> {
+        let uuid = Uuid::parse(uuid).unwrap();
+        validate(&uuid, policy).into_iter().map(|finding| finding.rule).collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let policy = Policy {
+            now_unix_millis: Some(1704288448382),
+            ..Policy::strict()
+        };
+
+        assert_eq!(rules("d71c7cd2-aa3b-11ee-ac4a-325096b39f47", &policy), []);
+        assert_eq!(rules("ffb82219-2be8-4961-8c83-2163e1b4b966", &policy), []);
+        assert_eq!(rules("a050b517-6677-5119-9a77-2d26bbf30507", &policy), []);
+        assert_eq!(rules("017f22e2-79b0-7cc3-98c4-dc0c0c07398f", &policy), []);
+        assert_eq!(rules("5c146b14-3c52-8afd-938a-375d0df1fbf6", &policy), []);
+        assert_eq!(rules("00000000-0000-0000-0000-000000000000", &policy), []);
+        assert_eq!(rules("ffffffff-ffff-ffff-ffff-ffffffffffff", &policy), []);
+    }
+
+    #[test]
+    fn test_findings() {
+        let policy = Policy {
+            now_unix_millis: Some(1704288448382),
+            ..Policy::default()
+        };
+
+        // Wrong variant
+        assert_eq!(
+            rules("d71c7cd2-aa3b-11ee-ec4a-325096b39f47", &policy),
+            [Rule::NonRfcVariant]
+        );
+
+        // Unknown version
+        assert_eq!(
+            rules("ffb82219-2be8-c961-8c83-2163e1b4b966", &policy),
+            [Rule::UnknownVersion]
+        );
+
+        // V4 with mostly zero bits, and with a long run of ones
+        assert_eq!(
+            rules("00000000-0000-4000-8000-000000000001", &policy),
+            [Rule::LowEntropy]
+        );
+        assert_eq!(
+            rules("ffffffff-ff3c-4961-8c83-2163e1b4b966", &policy),
+            [Rule::LowEntropy]
+        );
+
+        // A run of 30 zero bits is unlikely, but not enough to be flagged
+        assert_eq!(rules("00000003-2be8-4961-8c83-2163e1b4b966", &policy), []);
+
+        // V1 from the year 5236, and V7 from 1970
+        assert_eq!(
+            rules("fe4d0d06-adf3-1fff-bdd3-325096b39f47", &policy),
+            [Rule::FutureTimestamp]
+        );
+        assert_eq!(
+            rules("00000000-0001-7cc3-98c4-dc0c0c07398f", &policy),
+            [Rule::EarlyTimestamp]
+        );
+
+        // Disabled rules are not reported
+        let lenient = Policy {
+            low_entropy: None,
+            ..policy
+        };
+        assert_eq!(rules("00000000-0000-4000-8000-000000000001", &lenient), []);
+
+        // A skew large enough to overflow disables the check
+        let unbounded_skew = Policy {
+            max_clock_skew_millis: u64::MAX,
+            ..policy
+        };
+        assert_eq!(rules("fe4d0d06-adf3-1fff-bdd3-325096b39f47", &unbounded_skew), []);
+    }
+
+    #[test]
+    fn test_ensure_valid() {
+        let uuid = Uuid::parse("00000000-0000-4000-8000-000000000001").unwrap();
+
+        assert_eq!(ensure_valid(&uuid, &Policy::default()), Ok(()));
+
+        let error = ensure_valid(&uuid, &Policy::strict()).unwrap_err();
+        assert_eq!(
+            error.findings(),
+            [Finding {
+                rule: Rule::LowEntropy,
+                severity: Severity::Error
+            }]
+        );
+        assert_eq!(error.to_string(), "UUID violates 1 rule(s), first: Error: random bits have suspiciously low entropy");
+    }
+}
@@ -5,10 +5,12 @@
 //! A random V4 UUID can be generated as follows:
 //! 
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use uuidland::{Uuid, gen};
 //! 
 //! let uuid: Uuid = gen::v4();
 //! println!("{}", uuid);
+//! # }
 //! ```
 //! 
 //! UUIDs can also pe parsed from strings
@@ -17,15 +19,35 @@
 //! let uuid = Uuid::parse("fe4d0d06-adf3-1fff-bdd3-325096b39f47").unwrap();
 //! ```
 //! 
+//! UUID literals can be checked at compile time with the [`uuid!`] macro
+//! ```
+//! use uuidland::{uuid, Uuid};
+//! const NAMESPACE: Uuid = uuid!("fe4d0d06-adf3-1fff-bdd3-325096b39f47");
+//! ```
+//! 
 //! ## Generating UUIDs
 //! 
-//! This crate supports generating V1, V3, V4 and V5 UUIDs. Use the appropriate functions from `uuidland::gen::*` to generate UUIDs.
+//! This crate supports generating V1, V3, V4, V5, V6, V7 and V8 UUIDs. Use the appropriate functions from `uuidland::gen::*` to generate UUIDs.
 //! 
 //! * Time based (V1)
 //! 
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use uuidland::gen;
 //! let uuid_v1 = gen::v1().expect("Failed to generate UUID");
+//! # }
+//! ```
+//!
+//! * Time ordered (V6 / V7)
+//!
+//!    V6 reorders the V1 timestamp so that UUIDs sort by creation time, while V7 uses a Unix timestamp in milliseconds followed by random bits.
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! use uuidland::gen;
+//! let uuid_v6 = gen::v6().expect("Failed to generate UUID");
+//! let uuid_v7 = gen::v7().expect("Failed to generate UUID");
+//! # }
 //! ```
 //!
 //! * Hash Based (V3 / V5)
@@ -33,6 +55,7 @@
 //!    For versions 3 and 5, a namespace (another UUID) and name are also needed.
 //! 
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use uuidland::{Uuid, gen};
 //!
 //! // Some existing UUID
@@ -45,27 +68,153 @@
 //! 
 //! // Or pass None as namespace. In that case, a random UUID will be used as namespace
 //! let uuid_v5 = gen::v5(b"bar", None);
+//! # }
+//! ```
+//!
+//! * Name based with modern hashes (V8)
+//!
+//!    Following RFC 9562 Appendix B.2, V8 UUIDs can be derived from a namespace and name using SHA-256, SHA-512 or any other [`digest::Digest`].
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! use uuidland::{gen, wellknown};
+//! let uuid_v8 = gen::v8_sha256(b"www.example.com", Some(wellknown::NS_DNS));
+//! # }
 //! ```
 //!
 //! * Randomly Generated (V4)
 //! 
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use uuidland::gen;
 //! let uuid_v4 = gen::v4();
+//! # }
 //! ```
+//!
+//! # `no_std` support
+//!
+//! The crate depends on `alloc` only when the default `std` feature is disabled.
+//! Parsing, formatting, inspection and the [`NameBasedBuilder`] keep working. Name based
+//! UUIDs are generated with [`gen::v3_with`], [`gen::v5_with`] and the `gen::v8_*_with`
+//! functions, which take an explicit namespace, or at compile time with
+//! [`gen::v3_const`] and [`gen::v5_const`]. Random and time based UUIDs are
+//! generated by passing a [`time_based::Clock`] and a [`rand::RngCore`] to
+//! [`gen::v4_with_rng`] and the `generate_with` methods of [`time_based::V1Generator`],
+//! [`time_based::V6Generator`] and [`time_based::V7Generator`].
+//!
+//! ```
+//! use uuidland::time_based::V7Generator;
+//! use rand::rngs::mock::StepRng;
+//!
+//! # fn read_rtc_millis() -> u64 { 1704288448382 }
+//! let mut rng = StepRng::new(1, 1);
+//! let mut generator = V7Generator::new();
+//! let uuid = generator.generate_with(&read_rtc_millis, &mut rng).unwrap();
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub(crate) mod uuid;
 pub(crate) mod uuid_v4;
 pub(crate) mod hash_based;
 pub(crate) mod constants;
+pub(crate) mod const_hash;
+pub(crate) mod hex;
 pub mod time_based;
 pub mod inspect;
+pub mod encoding;
+pub mod typeid;
+pub mod distributions;
+pub mod scan;
+#[cfg(feature = "pseudonym")]
+pub mod pseudonym;
+#[cfg(feature = "cipher")]
+pub mod cipher;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+#[cfg(any(
+    feature = "postgres-types",
+    feature = "sqlx-postgres",
+    feature = "sqlx-mysql",
+    feature = "sqlx-sqlite",
+    feature = "rusqlite"
+))]
+pub mod db;
+#[cfg(feature = "secret")]
+pub mod secret;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "proptest")]
+pub mod proptest;
 
 pub mod gen {
-    pub use crate::time_based::v1;
+    #[cfg(feature = "std")]
+    pub use crate::time_based::{v1, v6, v7};
+    #[cfg(feature = "std")]
     pub use crate::uuid_v4::v4;
-    pub use crate::hash_based::{v3, v5};
+    #[cfg(feature = "std")]
+    pub use crate::hash_based::{v3, v5, v8_hash, v8_sha256, v8_sha512};
+
+    pub use crate::uuid_v4::v4_with_rng;
+    pub use crate::hash_based::{v3_with, v5_with, v8_hash_with, v8_sha256_with, v8_sha512_with};
+    pub use crate::hash_based::{v3_const, v5_const, NameBasedBuilder};
 }
 
 pub use uuid::{Uuid, wellknown};
+
+/// Parses a UUID literal at compile time. Invalid literals are rejected with a
+/// compile error.
+///
+/// ```
+/// use uuidland::{uuid, Uuid};
+///
+/// const NS_OURS: Uuid = uuid!("3f177ecc-9c78-4e9b-b142-1a8aea0e5624");
+/// let uuid = uuid!("fe4d0d06adf31fffbdd3325096b39f47");
+/// ```
+///
+/// ```compile_fail
+/// let uuid = uuidland::uuid!("fe4d0d06-adf3-1fff-bdd3-325096b39f4g");
+/// ```
+#[macro_export]
+macro_rules! uuid {
+    ($uuid:expr) => {{
+        const UUID: $crate::Uuid = $crate::Uuid::parse_const($uuid);
+        UUID
+    }};
+}
+
+/// Generates a V3 UUID from a namespace and a string name at compile time. The
+/// result is identical to that of [`gen::v3_with`].
+///
+/// ```
+/// use uuidland::{const_v3, wellknown, Uuid};
+///
+/// const NS_OURS: Uuid = const_v3!(wellknown::NS_DNS, "ourcompany.com");
+/// ```
+#[macro_export]
+macro_rules! const_v3 {
+    ($namespace:expr, $name:expr) => {{
+        const UUID: $crate::Uuid = $crate::gen::v3_const(::core::primitive::str::as_bytes($name), $namespace);
+        UUID
+    }};
+}
+
+/// Generates a V5 UUID from a namespace and a string name at compile time. The
+/// result is identical to that of [`gen::v5_with`].
+///
+/// ```
+/// use uuidland::{const_v5, gen, wellknown, Uuid};
+///
+/// const NS_OURS: Uuid = const_v5!(wellknown::NS_DNS, "ourcompany.com");
+/// assert_eq!(NS_OURS, gen::v5_with(b"ourcompany.com", wellknown::NS_DNS));
+/// ```
+#[macro_export]
+macro_rules! const_v5 {
+    ($namespace:expr, $name:expr) => {{
+        const UUID: $crate::Uuid = $crate::gen::v5_const(::core::primitive::str::as_bytes($name), $namespace);
+        UUID
+    }};
+}
 pub use gen::*;
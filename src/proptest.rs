@@ -0,0 +1,129 @@
+//! `proptest` strategies for UUIDs and their string forms, enabled by the `proptest`
+//! feature.
+//!
+//! ```
+//! use proptest::test_runner::TestRunner;
+//! use uuidland::proptest::{almost_valid_uuid_string, uuid_of_version};
+//! use uuidland::Uuid;
+//!
+//! let mut runner = TestRunner::default();
+//!
+//! runner.run(&almost_valid_uuid_string(), |input| {
+//!     assert!(Uuid::parse(&input).is_err());
+//!     Ok(())
+//! }).unwrap();
+//!
+//! runner.run(&uuid_of_version(7), |uuid| {
+//!     assert_eq!(uuid.details().version, 7);
+//!     Ok(())
+//! }).unwrap();
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::sample::{select, Index};
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use crate::uuid::Uuid;
+
+impl Arbitrary for Uuid {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Uuid>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<u128>().prop_map(Uuid::from_value).boxed()
+    }
+}
+
+/// Any 128-bit value, so every version and variant, including invalid ones
+pub fn any_uuid() -> impl Strategy<Value = Uuid> {
+    any::<Uuid>()
+}
+
+/// UUIDs with the given version and the RFC 9562 variant, with all other bits random.
+/// Panics if the version doesn't fit in 4 bits
+pub fn uuid_of_version(version: u8) -> impl Strategy<Value = Uuid> {
+    assert!(version <= 0xf, "The version is a 4-bit field");
+
+    any::<[u8; 16]>().prop_map(move |octets| Uuid::from_octets(octets, version))
+}
+
+/// Strings in every format accepted by [`Uuid::parse`], in lower or upper case
+pub fn valid_uuid_string() -> impl Strategy<Value = String> {
+    (any_uuid(), 0..3u8, any::<bool>()).prop_map(|(uuid, format, upper)| {
+        let formatted = match format {
+            0 => uuid.to_string_hex(),
+            1 => uuid.to_string_hex_joined(),
+            _ => format!("0x{}", uuid.to_string_hex_joined()),
+        };
+
+        if upper {
+            formatted.to_ascii_uppercase()
+        } else {
+            formatted
+        }
+    })
+}
+
+/// Strings one edit away from a valid UUID string, that [`Uuid::parse`] rejects: a
+/// character is removed, inserted or replaced
+pub fn almost_valid_uuid_string() -> impl Strategy<Value = String> {
+    // Characters that are never valid, or only valid at some positions
+    const CHARACTERS: [char; 10] = ['-', 'g', 'G', 'x', 'z', ' ', '{', '}', ':', 'é'];
+
+    let edit = (any::<Index>(), 0..3u8, select(&CHARACTERS[..]));
+
+    (valid_uuid_string(), edit)
+        .prop_map(|(mut value, (index, kind, character))| {
+            let at = char_boundary(&value, index.index(value.len() + 1));
+
+            match kind {
+                0 if at < value.len() => {
+                    value.remove(at);
+                }
+                1 => value.insert(at, character),
+                _ if at < value.len() => value.replace_range(at..at + 1, character.encode_utf8(&mut [0; 4])),
+                _ => value.push(character),
+            }
+
+            value
+        })
+        .prop_filter("Edits may keep the string valid", |value| Uuid::parse(value).is_err())
+}
+
+/// Moves the index back to the start of the character it falls into
+fn char_boundary(value: &str, mut index: usize) -> usize {
+    while !value.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_uuid_of_version(
+            (version, uuid) in (0..16u8).prop_flat_map(|version| (Just(version), uuid_of_version(version)))
+        ) {
+            prop_assert_eq!(uuid.details().version, version);
+            prop_assert_eq!(uuid.details().variant, 0x80);
+        }
+
+        #[test]
+        fn test_valid_uuid_string(value in valid_uuid_string()) {
+            prop_assert!(Uuid::parse(&value).is_ok(), "{}", value);
+        }
+
+        #[test]
+        fn test_almost_valid_uuid_string(value in almost_valid_uuid_string()) {
+            prop_assert!(Uuid::parse(&value).is_err(), "{}", value);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! Deterministic, keyed pseudonyms of UUIDs, e.g. to export datasets without the
+//! original user IDs.
+//!
+//! A pseudonym is the HMAC-SHA-256 of the 16 octets of the UUID, keyed with a secret
+//! key, and truncated to 128 bits. The same UUID and key always give the same
+//! pseudonym, while without the key pseudonyms can neither be linked back to the
+//! original UUIDs nor computed for other UUIDs. Since only the octets of the UUID are
+//! hashed, pseudonyms can be reproduced elsewhere, e.g. in Postgres with
+//! `hmac(uuid_send(id), key, 'sha256')` from `pgcrypto`.
+//!
+//! ```
+//! use uuidland::pseudonym::Pseudonymizer;
+//! use uuidland::wellknown;
+//!
+//! let pseudonymizer = Pseudonymizer::new(b"secret key");
+//!
+//! let pseudonym = pseudonymizer.v8(&wellknown::NS_DNS);
+//! assert_eq!(pseudonym.to_string_hex(), "c562452d-0dd9-80c7-9a51-cf7413d4e8d5");
+//!
+//! // The version of the original UUID is kept by the format preserving mode
+//! let pseudonym = pseudonymizer.preserving(&wellknown::NS_URL);
+//! assert_eq!(pseudonym.to_string_hex(), "81c5a467-e653-1452-868d-c23aa392f72f");
+//! ```
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::uuid::{Octets, Uuid};
+
+/// Maps UUIDs to pseudonyms keyed with a secret key
+#[derive(Clone)]
+pub struct Pseudonymizer {
+    mac: Hmac<Sha256>,
+}
+
+impl Pseudonymizer {
+    /// Creates a pseudonymizer with the given secret key. Keys of any length are
+    /// accepted, but should be at least 32 random bytes
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            mac: Hmac::new_from_slice(key).expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    /// Returns the pseudonym of `uuid` as a V8 UUID
+    pub fn v8(&self, uuid: &Uuid) -> Uuid {
+        Uuid::from_octets(self.hash(uuid), 8)
+    }
+
+    /// Returns the pseudonym of `uuid`, with the version and variant of `uuid`. This
+    /// keeps pseudonyms valid where only some versions are accepted, but the version
+    /// of the original UUID is revealed.
+    ///
+    /// The Nil and Max UUIDs are returned as they are, since they don't identify anything
+    pub fn preserving(&self, uuid: &Uuid) -> Uuid {
+        if uuid.is_nil() || uuid.is_max() {
+            return uuid.clone();
+        }
+
+        let original = uuid.value().to_be_bytes();
+        let mut octets = self.hash(uuid);
+
+        // The variant takes 1 to 3 bits, as many as needed to tell it apart
+        let variant_mask = match original[8] {
+            0x00..=0x7f => 0x80,
+            0x80..=0xbf => 0xc0,
+            _ => 0xe0,
+        };
+
+        octets[6] = (octets[6] & 0x0f) | (original[6] & 0xf0);
+        octets[8] = (octets[8] & !variant_mask) | (original[8] & variant_mask);
+
+        Uuid::from_value(u128::from_be_bytes(octets))
+    }
+
+    fn hash(&self, uuid: &Uuid) -> Octets {
+        let mut mac = self.mac.clone();
+        mac.update(&uuid.value().to_be_bytes());
+
+        mac.finalize().into_bytes()[0..16].try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wellknown;
+
+    #[test]
+    fn test_v8() {
+        let pseudonymizer = Pseudonymizer::new(b"secret key");
+        let pseudonym = pseudonymizer.v8(&wellknown::NS_DNS);
+
+        // HMAC-SHA-256 with Python's hmac module: c562452d0dd970c79a51cf7413d4e8d5
+        assert_eq!(pseudonym, crate::uuid!("c562452d-0dd9-80c7-9a51-cf7413d4e8d5"));
+        assert_eq!(pseudonymizer.v8(&wellknown::NS_DNS), pseudonym);
+
+        let details = pseudonymizer.v8(&wellknown::Nil).details();
+        assert_eq!((details.version, details.variant), (8, 0x80));
+
+        assert!(pseudonymizer.v8(&wellknown::NS_URL) != pseudonym);
+        assert!(Pseudonymizer::new(b"other key").v8(&wellknown::NS_DNS) != pseudonym);
+    }
+
+    #[test]
+    fn test_preserving() {
+        let pseudonymizer = Pseudonymizer::new(b"secret key");
+
+        assert_eq!(
+            pseudonymizer.preserving(&wellknown::NS_URL),
+            crate::uuid!("81c5a467-e653-1452-868d-c23aa392f72f")
+        );
+
+        // Every version, with the NCS, RFC, Microsoft and future variants
+        for version in 0..16u128 {
+            for variant in [0x00u128, 0x80, 0xc0, 0xe0] {
+                let uuid = Uuid::from_value(0x0123_4567_89ab_0def_1f23_4567_89ab_cdef | version << 76 | variant << 56);
+                let original = uuid.details();
+
+                let pseudonym = pseudonymizer.preserving(&uuid);
+                let details = pseudonym.details();
+
+                assert_eq!(details.version, original.version, "{uuid}");
+                assert_eq!(details.variant, original.variant, "{uuid}");
+                assert!(pseudonym != uuid);
+
+                // All other bits are the same as in the V8 pseudonym
+                let mask = !(0xf << 76 | 0xe0 << 56);
+                assert_eq!(pseudonym.value() & mask, pseudonymizer.v8(&uuid).value() & mask);
+            }
+        }
+
+        assert_eq!(pseudonymizer.preserving(&wellknown::Nil), wellknown::Nil);
+        assert_eq!(pseudonymizer.preserving(&wellknown::MAX), wellknown::MAX);
+    }
+}
@@ -0,0 +1,245 @@
+//! Finding UUIDs in text, such as logs.
+//!
+//! UUIDs are recognized in the 8-4-4-4-12 format, the 32-length hex string format,
+//! the braced format (`{8-4-4-4-12}`) and the URN format (`urn:uuid:8-4-4-4-12`), in
+//! upper or lower case. A UUID is only found on word boundaries: it must not be
+//! preceded or followed by an ASCII letter, digit or underscore. Dashes and other
+//! punctuation are boundaries, so `id-6ba7b810-9dad-11d1-80b4-00c04fd430c8` contains
+//! a UUID while `id6ba7b8109dad11d180b400c04fd430c8` does not.
+//!
+//! ```
+//! use uuidland::scan;
+//!
+//! let text = "user {6ba7b810-9dad-11d1-80b4-00c04fd430c8} logged in";
+//! let found: Vec<_> = scan::find_iter(text).collect();
+//!
+//! assert_eq!(found, [(5..43, uuidland::wellknown::NS_DNS)]);
+//! ```
+
+use alloc::string::{String, ToString};
+use core::ops::Range;
+
+use crate::uuid::Uuid;
+
+const URN_PREFIX: &[u8] = b"urn:uuid:";
+
+/// Returns an iterator over the UUIDs in `text`, along with the byte range of each of
+/// them. The range includes the braces or the URN prefix of UUIDs in those formats
+pub fn find_iter(text: &str) -> FindIter<'_> {
+    FindIter { text, position: 0 }
+}
+
+/// Iterator returned by [`find_iter`]
+#[derive(Clone, Debug)]
+pub struct FindIter<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Iterator for FindIter<'a> {
+    type Item = (Range<usize>, Uuid);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+
+        while self.position < bytes.len() {
+            let start = self.position;
+
+            if is_boundary_before(bytes, start) {
+                if let Some((uuid, range)) = uuid_at(bytes, start) {
+                    self.position = range.end;
+                    return Some((range, uuid));
+                }
+            }
+
+            // Positions inside a word are not boundaries, so skip the rest of it
+            self.position += 1;
+            while self.position < bytes.len() && is_word(bytes[self.position - 1]) {
+                self.position += 1;
+            }
+        }
+
+        None
+    }
+}
+
+/// Replaces every UUID found in `text` with the one returned by `replace`, keeping the
+/// format and case of the original. For example, to replace UUIDs with pseudonyms:
+///
+/// ```
+/// use uuidland::{gen, scan, wellknown};
+///
+/// let text = "user 6BA7B810-9DAD-11D1-80B4-00C04FD430C8 logged in";
+/// let scrubbed = scan::replace_all(text, |uuid| gen::v5_with(&uuid.value().to_be_bytes(), wellknown::NS_OID));
+///
+/// assert_eq!(scrubbed, "user 1F8A790E-ED7C-5B66-B71F-B0529B723E16 logged in");
+/// ```
+pub fn replace_all<F>(text: &str, mut replace: F) -> String
+where
+    F: FnMut(&Uuid) -> Uuid,
+{
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for (range, uuid) in find_iter(text) {
+        output.push_str(&text[last..range.start]);
+        output.push_str(&format_like(&text[range.clone()], &replace(&uuid)));
+        last = range.end;
+    }
+
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Formats `uuid` like `found`, a match of [`find_iter`]
+fn format_like(found: &str, uuid: &Uuid) -> String {
+    let mut start = 0;
+    let mut end = found.len();
+
+    if found.starts_with('{') {
+        start += 1;
+        end -= 1;
+    }
+
+    // The URN prefix is the only one starting with a character other than a hex digit
+    if !found.as_bytes()[start].is_ascii_hexdigit() {
+        start += URN_PREFIX.len();
+    }
+
+    let hex = &found[start..end];
+
+    let mut formatted = match hex.len() {
+        36 => uuid.to_string_hex(),
+        _ => uuid.to_string_hex_joined(),
+    };
+
+    if hex.bytes().any(|ch| ch.is_ascii_uppercase()) && !hex.bytes().any(|ch| ch.is_ascii_lowercase()) {
+        formatted.make_ascii_uppercase();
+    }
+
+    found[..start].to_string() + &formatted + &found[end..]
+}
+
+fn is_word(ch: u8) -> bool {
+    ch.is_ascii_alphanumeric() || ch == b'_'
+}
+
+fn is_boundary_before(bytes: &[u8], index: usize) -> bool {
+    index == 0 || !is_word(bytes[index - 1])
+}
+
+fn is_boundary_after(bytes: &[u8], index: usize) -> bool {
+    !matches!(bytes.get(index), Some(&ch) if is_word(ch))
+}
+
+/// Parses a UUID starting at `start`, which is on a word boundary. Returns it along
+/// with its range, extended to the URN prefix and braces around it
+fn uuid_at(bytes: &[u8], start: usize) -> Option<(Uuid, Range<usize>)> {
+    if !bytes[start].is_ascii_hexdigit() {
+        return None;
+    }
+
+    let (uuid, len) = [36, 32].into_iter().find_map(|len| {
+        let candidate = bytes.get(start..start + len)?;
+
+        if !is_boundary_after(bytes, start + len) {
+            return None;
+        }
+
+        Some((Uuid::parse_ascii(candidate).ok()?, len))
+    })?;
+
+    let mut range = start..start + len;
+
+    // Only the 8-4-4-4-12 format may have a URN prefix or braces
+    if len == 36 {
+        let prefix_start = start.saturating_sub(URN_PREFIX.len());
+
+        if start >= URN_PREFIX.len()
+            && bytes[prefix_start..start].eq_ignore_ascii_case(URN_PREFIX)
+            && is_boundary_before(bytes, prefix_start)
+        {
+            range.start = prefix_start;
+        }
+
+        if range.start > 0 && bytes[range.start - 1] == b'{' && bytes.get(range.end) == Some(&b'}') {
+            range = range.start - 1..range.end + 1;
+        }
+    }
+
+    Some((uuid, range))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wellknown::{NS_DNS, NS_URL};
+    use alloc::vec::Vec;
+
+    fn find(text: &str) -> Vec<(&str, Uuid)> {
+        find_iter(text).map(|(range, uuid)| (&text[range], uuid)).collect()
+    }
+
+    #[test]
+    fn test_formats() {
+        let text = "a 6ba7b810-9dad-11d1-80b4-00c04fd430c8, b 6BA7B8119DAD11D180B400C04FD430C8; \
+                    c {6ba7b810-9dad-11d1-80b4-00c04fd430c8} d URN:UUID:6ba7b811-9dad-11d1-80b4-00c04fd430c8.";
+
+        assert_eq!(
+            find(text),
+            [
+                ("6ba7b810-9dad-11d1-80b4-00c04fd430c8", NS_DNS),
+                ("6BA7B8119DAD11D180B400C04FD430C8", NS_URL),
+                ("{6ba7b810-9dad-11d1-80b4-00c04fd430c8}", NS_DNS),
+                ("URN:UUID:6ba7b811-9dad-11d1-80b4-00c04fd430c8", NS_URL),
+            ]
+        );
+
+        assert_eq!(
+            find("{urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8}"),
+            [("{urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8}", NS_DNS)]
+        );
+    }
+
+    #[test]
+    fn test_boundaries() {
+        // Adjacent to punctuation, or at the start and end of the text
+        assert_eq!(find("6ba7b810-9dad-11d1-80b4-00c04fd430c8").len(), 1);
+        assert_eq!(find("id=6ba7b810-9dad-11d1-80b4-00c04fd430c8&x").len(), 1);
+        assert_eq!(find("id-6ba7b810-9dad-11d1-80b4-00c04fd430c8-1").len(), 1);
+        assert_eq!(find("é6ba7b810-9dad-11d1-80b4-00c04fd430c8é").len(), 1);
+
+        // Inside words or longer hex strings
+        assert_eq!(find("x6ba7b810-9dad-11d1-80b4-00c04fd430c8"), []);
+        assert_eq!(find("6ba7b810-9dad-11d1-80b4-00c04fd430c8_"), []);
+        assert_eq!(find("6ba7b8109dad11d180b400c04fd430c8a"), []);
+        assert_eq!(find("0x6ba7b8109dad11d180b400c04fd430c8"), []);
+        assert_eq!(find("sha256:6ba7b8109dad11d180b400c04fd430c86ba7b8109dad11d180b400c04fd430c8"), []);
+
+        // Incomplete braces and URN prefixes are left out of the match
+        assert_eq!(
+            find("{6ba7b810-9dad-11d1-80b4-00c04fd430c8 xurn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+            [("6ba7b810-9dad-11d1-80b4-00c04fd430c8", NS_DNS), ("6ba7b810-9dad-11d1-80b4-00c04fd430c8", NS_DNS)]
+        );
+
+        // Consecutive UUIDs
+        assert_eq!(
+            find("6ba7b810-9dad-11d1-80b4-00c04fd430c8-6ba7b811-9dad-11d1-80b4-00c04fd430c8"),
+            [("6ba7b810-9dad-11d1-80b4-00c04fd430c8", NS_DNS), ("6ba7b811-9dad-11d1-80b4-00c04fd430c8", NS_URL)]
+        );
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let text = "a 6ba7b810-9dad-11d1-80b4-00c04fd430c8 b 6BA7B8109DAD11D180B400C04FD430C8 \
+                    c {6ba7b810-9dad-11d1-80b4-00c04fd430c8} d urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+
+        assert_eq!(
+            replace_all(text, |_| NS_URL),
+            "a 6ba7b811-9dad-11d1-80b4-00c04fd430c8 b 6BA7B8119DAD11D180B400C04FD430C8 \
+             c {6ba7b811-9dad-11d1-80b4-00c04fd430c8} d urn:uuid:6ba7b811-9dad-11d1-80b4-00c04fd430c8"
+        );
+
+        assert_eq!(replace_all("no uuids", |_| NS_URL), "no uuids");
+    }
+}
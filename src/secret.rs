@@ -0,0 +1,129 @@
+//! UUIDs used as secrets, such as bearer tokens in password reset links.
+//!
+//! [`SecretUuid`] wraps a random V4 [`Uuid`] that is compared in constant time, hidden
+//! from `Debug` output and overwritten with zeros when dropped. It is only available
+//! with the `secret` feature.
+//!
+//! ```
+//! use uuidland::secret::SecretUuid;
+//!
+//! let token = SecretUuid::generate();
+//! let link = format!("https://example.com/reset/{}", token.to_string_hex());
+//!
+//! let received = SecretUuid::parse(link.rsplit('/').next().unwrap()).unwrap();
+//! assert!(received == token);
+//! assert_eq!(format!("{received:?}"), "SecretUuid(..)");
+//! ```
+
+use alloc::string::String;
+use core::fmt::Debug;
+
+use rand::rngs::OsRng;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::uuid::Uuid;
+
+/// A V4 UUID kept as a secret
+///
+/// Only the comparison is constant-time: parsing and formatting go through the same
+/// code as [`Uuid`], and the strings they take or return are not zeroized.
+pub struct SecretUuid(Uuid);
+
+impl SecretUuid {
+    /// Generates a new V4 UUID from the operating system's random number generator
+    pub fn generate() -> Self {
+        Self(crate::gen::v4_with_rng(&mut OsRng))
+    }
+
+    /// Parses a secret UUID, accepting the same formats as [`Uuid::parse`]
+    #[allow(clippy::result_unit_err)]
+    pub fn parse<T: AsRef<str>>(value: T) -> Result<Self, ()> {
+        Uuid::parse(value).map(Self)
+    }
+
+    /// Formats the secret in the 8-4-4-4-12 format
+    pub fn to_string_hex(&self) -> String {
+        self.0.to_string_hex()
+    }
+
+    /// Gives access to the secret UUID. Comparing the returned value is not constant-time
+    pub fn expose_secret(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl ConstantTimeEq for SecretUuid {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.value().to_be_bytes().ct_eq(&other.0.value().to_be_bytes())
+    }
+}
+
+impl PartialEq for SecretUuid {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for SecretUuid {}
+
+impl Debug for SecretUuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SecretUuid(..)")
+    }
+}
+
+impl Zeroize for SecretUuid {
+    fn zeroize(&mut self) {
+        self.0 .0.zeroize();
+    }
+}
+
+impl Drop for SecretUuid {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretUuid {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let first = SecretUuid::generate();
+        let second = SecretUuid::generate();
+
+        assert_eq!(first.expose_secret().details().version, 4);
+        assert_eq!(first.expose_secret().details().variant, 0x80);
+        assert!(first != second);
+    }
+
+    #[test]
+    fn test_parse_and_compare() {
+        let secret = SecretUuid::parse("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+
+        assert_eq!(secret.to_string_hex(), "6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+        assert!(bool::from(secret.ct_eq(&SecretUuid::parse("6ba7b8109dad11d180b400c04fd430c8").unwrap())));
+        assert!(!bool::from(secret.ct_eq(&SecretUuid::parse("6ba7b810-9dad-11d1-80b4-00c04fd430c9").unwrap())));
+        assert!(SecretUuid::parse("not a uuid").is_err());
+    }
+
+    #[test]
+    fn test_debug_redacted() {
+        let secret = SecretUuid::parse("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+
+        assert_eq!(format!("{secret:?}"), "SecretUuid(..)");
+        assert_eq!(format!("{:?}", Some(secret)), "Some(SecretUuid(..))");
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut secret = SecretUuid::generate();
+        secret.zeroize();
+
+        assert!(secret.expose_secret().is_nil());
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "std")]
 use core::cell::RefCell;
 
 use rand::RngCore;
@@ -10,9 +11,11 @@ pub trait NodeIdProvider {
 }
 
 /// A ['NodeIdProvider'] that gives out random node ID's
+#[cfg(feature = "std")]
 #[derive(Clone, Copy)]
 pub struct RandomNodeIdProvider;
 
+#[cfg(feature = "std")]
 impl NodeIdProvider for RandomNodeIdProvider {
     fn get_node_id(&self) -> u64 {
         // Per Section 4.5:
@@ -38,7 +41,44 @@ impl NodeIdProvider for StaticNodeIdProvider {
     }
 }
 
-#[derive(thiserror::Error, Debug)]
+/// Provides the current time for time based UUID generators
+///
+/// With the `std` feature enabled, [`SystemClock`] is used by default. On `no_std`
+/// targets, a clock must be supplied to the `*_with` methods of the generators. Any
+/// `Fn() -> u64` returning the Unix time in milliseconds can be used as a clock.
+pub trait Clock {
+    /// Returns the number of milliseconds elapsed since 00:00:00.00, 01 January 1970
+    fn unix_millis(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn unix_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// A [`Clock`] that reads the system time
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn unix_millis(&self) -> u64 {
+        #[cfg(feature = "webtime")]
+        use web_time::{SystemTime, UNIX_EPOCH};
+
+        #[cfg(not(feature = "webtime"))]
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     #[error("Too many UUIDs generated in a single time interval")]
     TooManyGenerated,
@@ -68,27 +108,18 @@ impl<const V: u8, P> TimeBasedGenerator<V, P>
 where
     P: NodeIdProvider,
 {
-    fn get_time_milli() -> u64 {
-        #[cfg(feature = "webtime")]
-        use web_time::{SystemTime, UNIX_EPOCH};
-
-        #[cfg(not(feature = "webtime"))]
-        use std::time::{SystemTime, UNIX_EPOCH};
-
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64
-    }
-
-    fn new(node_id_provider: P) -> Self {
+    fn new_with<C, R>(node_id_provider: P, clock: &C, rng: &mut R) -> Self
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
         let node_id = node_id_provider.get_node_id();
         Self {
             node_id_provider,
             state: TimeBasedState {
                 node_id,
-                time_msec: Self::get_time_milli(),
-                clock_seq: (rand::thread_rng().next_u32() & 0x0000ffff) as u16,
+                time_msec: clock.unix_millis(),
+                clock_seq: (rng.next_u32() & 0x0000ffff) as u16,
                 generated_count: 0,
             },
         }
@@ -101,15 +132,20 @@ where
         }
     }
 
-    fn generate(&mut self) -> Result<Uuid, Error> {
+    fn generate_with<C, R>(&mut self, clock: &C, rng: &mut R) -> Result<Uuid, Error>
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
         // Get the current timestamp
-        let msec = Self::get_time_milli();
+        let msec = clock.unix_millis();
 
         // The returned UUID is calculated from *current state*, not the next state
         let octets = Self::layout_octets(&self.state);
 
         // Update the state for the next UUID
-        let next_state = Self::tick(&self.state, self.node_id_provider.get_node_id(), msec)?;
+        let node_id = self.node_id_provider.get_node_id();
+        let next_state = Self::tick(&self.state, node_id, msec, rng)?;
         self.state = next_state;
 
         Ok(Uuid::from_octets(octets, V))
@@ -137,17 +173,29 @@ where
 
         let mut octets = Octets::default();
 
-        // Set the time_low field equal to the least significant 32
-        // bits of the timestamp
-        octets[0..=3].copy_from_slice(&ts_bytes[4..=7]);
+        if V == 6 {
+            // Version 6 stores the same timestamp with the most significant
+            // bits first (RFC 9562 Section 5.6), so that UUIDs sort by time.
+            //
+            // The first 48 bits hold bits 12 through 59 of the timestamp
+            octets[0..=5].copy_from_slice(&(ts >> 12).to_be_bytes()[2..=7]);
 
-        // Set the time_mid field equal to bits 32 through 47 of the timestamp
-        octets[4..=5].copy_from_slice(&ts_bytes[2..=3]);
+            // The 12 least significant bits of the timestamp follow the
+            // version bits, which are overwritten later.
+            octets[6..=7].copy_from_slice(&((ts & 0x0fff) as u16).to_be_bytes());
+        } else {
+            // Set the time_low field equal to the least significant 32
+            // bits of the timestamp
+            octets[0..=3].copy_from_slice(&ts_bytes[4..=7]);
 
-        // Set the 12 least significant bits (bits 0 through 11) of the
-        // time_hi_and_version field equal to bits 48 through 59 from the
-        // timestamp. The remaining bits are overwritten later.
-        octets[6..=7].copy_from_slice(&ts_bytes[0..=1]);
+            // Set the time_mid field equal to bits 32 through 47 of the timestamp
+            octets[4..=5].copy_from_slice(&ts_bytes[2..=3]);
+
+            // Set the 12 least significant bits (bits 0 through 11) of the
+            // time_hi_and_version field equal to bits 48 through 59 from the
+            // timestamp. The remaining bits are overwritten later.
+            octets[6..=7].copy_from_slice(&ts_bytes[0..=1]);
+        }
 
         // Set the clock_seq_low field to the eight least significant bits
         // (bits zero through 7) of the clock sequence.
@@ -166,14 +214,22 @@ where
         octets
     }
 
-    fn tick(state: &TimeBasedState, node_id: u64, msec: u64) -> Result<TimeBasedState, Error> {
+    fn tick<R>(
+        state: &TimeBasedState,
+        node_id: u64,
+        msec: u64,
+        rng: &mut R,
+    ) -> Result<TimeBasedState, Error>
+    where
+        R: RngCore + ?Sized,
+    {
         let last_msec = state.time_msec;
         let mut clock_seq = state.clock_seq;
         let mut generated_count = state.generated_count;
 
         // If the node_id has changed, then reset clock_seq with a random value.
         if state.node_id != node_id {
-            clock_seq = (rand::thread_rng().next_u32() & 0x0000ffff) as u16;
+            clock_seq = (rng.next_u32() & 0x0000ffff) as u16;
         }
 
         // Clock has regressed. Bump clock sequence
@@ -209,9 +265,21 @@ impl<P> V1Generator<P>
 where
     P: NodeIdProvider,
 {
+    #[cfg(feature = "std")]
     #[inline(always)]
     pub fn new(node_id_provider: P) -> Self {
-        Self(TimeBasedGenerator::new(node_id_provider))
+        Self::new_with(node_id_provider, &SystemClock, &mut rand::thread_rng())
+    }
+
+    /// Creates a generator that reads its initial state from the given clock and
+    /// random number generator
+    #[inline(always)]
+    pub fn new_with<C, R>(node_id_provider: P, clock: &C, rng: &mut R) -> Self
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
+        Self(TimeBasedGenerator::new_with(node_id_provider, clock, rng))
     }
 
     #[allow(dead_code)]
@@ -224,24 +292,241 @@ where
     }
 
     /// Generates a new Time Based UUID
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn generate(&mut self) -> Result<Uuid, Error> {
+        self.generate_with(&SystemClock, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Time Based UUID using the given clock and random number generator
+    #[inline(always)]
+    pub fn generate_with<C, R>(&mut self, clock: &C, rng: &mut R) -> Result<Uuid, Error>
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
+        self.0.generate_with(clock, rng)
+    }
+}
+
+/// Generator for version 6 UUIDs. These contain the same fields as version 1 UUIDs,
+/// but the timestamp is stored with its most significant bits first
+#[repr(transparent)]
+pub struct V6Generator<P>(TimeBasedGenerator<6, P>);
+
+impl<P> V6Generator<P>
+where
+    P: NodeIdProvider,
+{
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn new(node_id_provider: P) -> Self {
+        Self::new_with(node_id_provider, &SystemClock, &mut rand::thread_rng())
+    }
+
+    /// Creates a generator that reads its initial state from the given clock and
+    /// random number generator
+    #[inline(always)]
+    pub fn new_with<C, R>(node_id_provider: P, clock: &C, rng: &mut R) -> Self
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
+        Self(TimeBasedGenerator::new_with(node_id_provider, clock, rng))
+    }
+
+    #[allow(dead_code)]
+    #[inline(always)]
+    fn new_with_state(node_id_provider: P, state: TimeBasedState) -> Self {
+        Self(TimeBasedGenerator::new_with_state(node_id_provider, state))
+    }
+
+    /// Generates a new reordered Time Based UUID
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn generate(&mut self) -> Result<Uuid, Error> {
+        self.generate_with(&SystemClock, &mut rand::thread_rng())
+    }
+
+    /// Generates a new reordered Time Based UUID using the given clock and random
+    /// number generator
+    #[inline(always)]
+    pub fn generate_with<C, R>(&mut self, clock: &C, rng: &mut R) -> Result<Uuid, Error>
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
+        self.0.generate_with(clock, rng)
+    }
+}
+
+/// Generator for version 7 UUIDs (RFC 9562 Section 5.7). These start with a 48-bit
+/// Unix timestamp in milliseconds, followed by random bits.
+///
+/// UUIDs generated within the same millisecond are kept in increasing order with a
+/// 12-bit counter stored in the `rand_a` field (RFC 9562 Section 6.2, Method 1). The
+/// counter starts at a random value in the lower half of its range.
+#[derive(Clone, Default)]
+pub struct V7Generator {
+    // Unix timestamp in milliseconds of last generated UUID
+    time_msec: u64,
+
+    // The 12-bit counter of last generated UUID
+    counter: u16,
+}
+
+impl V7Generator {
+    pub const fn new() -> Self {
+        Self {
+            time_msec: 0,
+            counter: 0,
+        }
+    }
+
+    /// Generates a new Unix Epoch time based UUID
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn generate(&mut self) -> Result<Uuid, Error> {
+        self.generate_with(&SystemClock, &mut rand::thread_rng())
+    }
+
+    /// Generates a new Unix Epoch time based UUID using the given clock and random
+    /// number generator
+    pub fn generate_with<C, R>(&mut self, clock: &C, rng: &mut R) -> Result<Uuid, Error>
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
+        let msec = clock.unix_millis();
+
+        if msec > self.time_msec {
+            self.time_msec = msec;
+            self.counter = (rng.next_u32() & 0x07ff) as u16;
+        } else {
+            // Either the same millisecond, or the clock has regressed. Keep the
+            // last timestamp and bump the counter so that the output stays ordered
+            if self.counter >= 0x0fff {
+                return Err(Error::TooManyGenerated);
+            }
+
+            self.counter += 1;
+        }
+
+        let mut octets = Octets::default();
+
+        // Random bits of the rand_b field. The variant bits are overwritten later.
+        rng.fill_bytes(&mut octets[8..=15]);
+
+        // 48-bit big-endian Unix timestamp in milliseconds
+        octets[0..=5].copy_from_slice(&self.time_msec.to_be_bytes()[2..=7]);
+
+        // The counter goes into the rand_a field, after the version bits
+        octets[6..=7].copy_from_slice(&self.counter.to_be_bytes());
+
+        Ok(Uuid::from_octets(octets, 7))
+    }
+}
+
+/// Generator for version 7 UUIDs that follows the monotonic generation of ULIDs.
+///
+/// The UUIDs start with a 48-bit Unix timestamp in milliseconds like ULIDs, so their
+/// ULID form (see [`Uuid::to_ulid_string`]) sorts in the same order as the UUIDs. The
+/// remaining 74 bits (the `rand_a` and `rand_b` fields) are random for the first
+/// UUID of every millisecond, and are incremented by one for every further UUID in the
+/// same millisecond, as done by monotonic ULID generators.
+#[derive(Clone, Default)]
+pub struct UlidGenerator {
+    // Unix timestamp in milliseconds of last generated UUID
+    time_msec: u64,
+
+    // The 74 random bits of last generated UUID
+    random: u128,
+}
+
+impl UlidGenerator {
+    pub const fn new() -> Self {
+        Self {
+            time_msec: 0,
+            random: 0,
+        }
+    }
+
+    /// Generates a new monotonic Unix Epoch time based UUID
+    #[cfg(feature = "std")]
     #[inline(always)]
     pub fn generate(&mut self) -> Result<Uuid, Error> {
-        self.0.generate()
+        self.generate_with(&SystemClock, &mut rand::thread_rng())
+    }
+
+    /// Generates a new monotonic Unix Epoch time based UUID using the given clock and
+    /// random number generator
+    pub fn generate_with<C, R>(&mut self, clock: &C, rng: &mut R) -> Result<Uuid, Error>
+    where
+        C: Clock + ?Sized,
+        R: RngCore + ?Sized,
+    {
+        const RANDOM_MASK: u128 = (1 << 74) - 1;
+
+        let msec = clock.unix_millis();
+
+        if msec > self.time_msec {
+            let mut octets = Octets::default();
+            rng.fill_bytes(&mut octets);
+
+            self.time_msec = msec;
+            self.random = u128::from_be_bytes(octets) & RANDOM_MASK;
+        } else {
+            // Either the same millisecond, or the clock has regressed. Keep the
+            // last timestamp and increment the random bits
+            if self.random == RANDOM_MASK {
+                return Err(Error::TooManyGenerated);
+            }
+
+            self.random += 1;
+        }
+
+        // Spread the 74 bits over the rand_a and rand_b fields, around the
+        // version and variant bits
+        let rand_a = self.random >> 62;
+        let rand_b = self.random & ((1 << 62) - 1);
+        let value = (self.time_msec as u128) << 80 | rand_a << 64 | rand_b;
+
+        Ok(Uuid::from_octets(value.to_be_bytes(), 7))
     }
 }
 
+#[cfg(feature = "std")]
 thread_local! {
     static GLOBAL_GENERATOR_V1: RefCell<V1Generator<RandomNodeIdProvider>> = RefCell::new(
         V1Generator::new(
             RandomNodeIdProvider
         )
     );
+
+    static GLOBAL_GENERATOR_V6: RefCell<V6Generator<RandomNodeIdProvider>> = RefCell::new(
+        V6Generator::new(
+            RandomNodeIdProvider
+        )
+    );
+
+    static GLOBAL_GENERATOR_V7: RefCell<V7Generator> = const { RefCell::new(V7Generator::new()) };
 }
 
+#[cfg(feature = "std")]
 pub fn v1() -> Result<Uuid, Error> {
     GLOBAL_GENERATOR_V1.with(|generator| generator.borrow_mut().generate())
 }
 
+#[cfg(feature = "std")]
+pub fn v6() -> Result<Uuid, Error> {
+    GLOBAL_GENERATOR_V6.with(|generator| generator.borrow_mut().generate())
+}
+
+#[cfg(feature = "std")]
+pub fn v7() -> Result<Uuid, Error> {
+    GLOBAL_GENERATOR_V7.with(|generator| generator.borrow_mut().generate())
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
@@ -276,10 +561,95 @@ mod tests {
 
         let mut generator = V1Generator::new_with_state(node_id_provider, state);
 
-        let value = generator.generate().unwrap();
+        let value = generator.generate_with(&|| time_msec, &mut rand::rngs::mock::StepRng::new(0, 1)).unwrap();
         assert_eq!(
             value.to_string_hex(),
             "d71c7cd2-aa3b-11ee-ac4a-325096b39f47"
         );
     }
+
+    #[test]
+    fn test_v6_output() {
+        // Same contents as the reference UUID of `test_output`, with the
+        // timestamp reordered as in RFC 9562 Appendix A.5
+        let node_id: u64 = 0x_32_50_96_B3_9F_47;
+
+        let state = TimeBasedState {
+            node_id,
+            time_msec: 1704288448382,
+            clock_seq: 11338,
+            generated_count: 4850,
+        };
+
+        let mut generator = V6Generator::new_with_state(StaticNodeIdProvider(node_id), state);
+        let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+
+        let value = generator.generate_with(&|| 1704288448382, &mut rng).unwrap();
+        assert_eq!(
+            value.to_string_hex(),
+            "1eeaa3bd-71c7-6cd2-ac4a-325096b39f47"
+        );
+    }
+
+    #[test]
+    fn test_v7_output() {
+        let mut generator = V7Generator::new();
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+
+        // Timestamp from RFC 9562 Appendix A.6
+        let value = generator.generate_with(&|| 0x0189_96DD_6ED0, &mut rng).unwrap();
+        assert_eq!(
+            value.to_string_hex(),
+            "018996dd-6ed0-7000-8000-000000000000"
+        );
+
+        // Within the same millisecond the counter keeps the output ordered
+        let next = generator.generate_with(&|| 0x0189_96DD_6ED0, &mut rng).unwrap();
+        assert_eq!(next.to_string_hex(), "018996dd-6ed0-7001-8000-000000000000");
+
+        // Also when the clock regresses
+        let next = generator.generate_with(&|| 0x0189_96DD_6000, &mut rng).unwrap();
+        assert_eq!(next.to_string_hex(), "018996dd-6ed0-7002-8000-000000000000");
+    }
+
+    #[test]
+    fn test_ulid_output() {
+        let mut generator = UlidGenerator::new();
+        let mut rng = rand::rngs::mock::StepRng::new(u64::MAX, 0);
+
+        let value = generator.generate_with(&|| 1469918176385, &mut rng).unwrap();
+        assert_eq!(value.to_string_hex(), "01563df3-6481-7fff-bfff-ffffffffffff");
+        assert_eq!(value.to_ulid_string(), "01ARYZ6S41FZZVZZZZZZZZZZZZ");
+
+        // The random bits are incremented within the same millisecond, carrying
+        // across the version and variant bits
+        generator.random = (1 << 62) - 1;
+        let next = generator.generate_with(&|| 1469918176385, &mut rng).unwrap();
+        assert_eq!(next.to_string_hex(), "01563df3-6481-7001-8000-000000000000");
+
+        let next = generator.generate_with(&|| 1469918176000, &mut rng).unwrap();
+        assert_eq!(next.to_string_hex(), "01563df3-6481-7001-8000-000000000001");
+        assert_eq!(next.details().ulid_timestamp(), crate::inspect::Timestamp::UnixMillis(1469918176385));
+
+        generator.random = (1 << 74) - 1;
+        assert!(generator.generate_with(&|| 1469918176385, &mut rng).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ordering() {
+        let first = v6().unwrap();
+        let second = v6().unwrap();
+        assert!(first.value() < second.value());
+
+        let first = v7().unwrap();
+        let second = v7().unwrap();
+        assert!(first.value() < second.value());
+
+        let mut generator = UlidGenerator::new();
+        let first = generator.generate().unwrap();
+        let second = generator.generate().unwrap();
+        assert!(first.value() < second.value());
+        assert!(first.to_ulid_string() < second.to_ulid_string());
+    }
 }
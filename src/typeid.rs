@@ -0,0 +1,206 @@
+//! Type-prefixed, sortable identifiers following the
+//! [TypeID specification](https://github.com/jetify-com/typeid/tree/main/spec) (version 0.3).
+//!
+//! A TypeID is a type prefix followed by an underscore and a UUID in lower case
+//! Crockford's Base32, such as `user_01h455vb4pex5vsknk084sn02q`. The prefix may be
+//! empty, in which case the underscore is omitted as well.
+
+use alloc::string::String;
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::encoding::DecodeError;
+use crate::uuid::Uuid;
+
+/// Maximum length of a TypeID prefix
+const MAX_PREFIX_LEN: usize = 63;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum TypeIdError {
+    #[error("Prefix must be at most 63 lower case ASCII letters or underscores, and not start or end with an underscore")]
+    InvalidPrefix,
+
+    #[error("Invalid TypeID suffix: {0}")]
+    InvalidSuffix(#[from] DecodeError),
+
+    #[error(transparent)]
+    Generate(#[from] crate::time_based::Error),
+}
+
+/// A TypeID, made of a type prefix and a UUID
+///
+/// ```
+/// use uuidland::typeid::TypeId;
+///
+/// let id: TypeId = "user_01h455vb4pex5vsknk084sn02q".parse().unwrap();
+///
+/// assert_eq!(id.prefix(), "user");
+/// assert_eq!(id.uuid().to_string_hex(), "01890a5d-ac96-774b-bcce-b302099a8057");
+/// assert_eq!(id.to_string(), "user_01h455vb4pex5vsknk084sn02q");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeId {
+    prefix: String,
+    uuid: Uuid,
+}
+
+impl TypeId {
+    /// Creates a TypeID with a new V7 UUID
+    #[cfg(feature = "std")]
+    pub fn new(prefix: &str) -> Result<Self, TypeIdError> {
+        Self::from_uuid(prefix, crate::gen::v7()?)
+    }
+
+    /// Creates a TypeID from an existing UUID. The specification recommends V7 UUIDs,
+    /// but any UUID is accepted
+    pub fn from_uuid(prefix: &str, uuid: Uuid) -> Result<Self, TypeIdError> {
+        validate_prefix(prefix)?;
+
+        Ok(Self {
+            prefix: prefix.into(),
+            uuid,
+        })
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+}
+
+fn validate_prefix(prefix: &str) -> Result<(), TypeIdError> {
+    let valid = prefix.len() <= MAX_PREFIX_LEN
+        && prefix.bytes().all(|ch| ch.is_ascii_lowercase() || ch == b'_')
+        && !prefix.starts_with('_')
+        && !prefix.ends_with('_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(TypeIdError::InvalidPrefix)
+    }
+}
+
+impl Display for TypeId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if !self.prefix.is_empty() {
+            write!(f, "{}_", self.prefix)?;
+        }
+
+        write!(f, "{}", self.uuid.to_base32().to_ascii_lowercase())
+    }
+}
+
+impl FromStr for TypeId {
+    type Err = TypeIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // The suffix never contains an underscore, while the prefix may
+        let (prefix, suffix) = match value.rsplit_once('_') {
+            // An underscore requires a non-empty prefix
+            Some(("", _)) => return Err(TypeIdError::InvalidPrefix),
+            Some((prefix, suffix)) => (prefix, suffix),
+            None => ("", value),
+        };
+
+        // The suffix is always encoded in lower case
+        if let Some(index) = suffix.bytes().position(|ch| ch.is_ascii_uppercase()) {
+            return Err(crate::encoding::invalid_character(suffix, index).into());
+        }
+
+        Self::from_uuid(prefix, Uuid::from_base32(suffix)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_valid() {
+        // Test vectors from valid.yml of the specification
+        let valid = [
+            ("00000000000000000000000000", "", "00000000-0000-0000-0000-000000000000"),
+            ("00000000000000000000000001", "", "00000000-0000-0000-0000-000000000001"),
+            ("0000000000000000000000000a", "", "00000000-0000-0000-0000-00000000000a"),
+            ("0000000000000000000000000g", "", "00000000-0000-0000-0000-000000000010"),
+            ("00000000000000000000000010", "", "00000000-0000-0000-0000-000000000020"),
+            ("7zzzzzzzzzzzzzzzzzzzzzzzzz", "", "ffffffff-ffff-ffff-ffff-ffffffffffff"),
+            (
+                "prefix_0123456789abcdefghjkmnpqrs",
+                "prefix",
+                "0110c853-1d09-52d8-d73e-1194e95b5f19",
+            ),
+            (
+                "prefix_01h455vb4pex5vsknk084sn02q",
+                "prefix",
+                "01890a5d-ac96-774b-bcce-b302099a8057",
+            ),
+            (
+                "pre_fix_00000000000000000000000000",
+                "pre_fix",
+                "00000000-0000-0000-0000-000000000000",
+            ),
+        ];
+
+        for (typeid, prefix, uuid) in valid {
+            let parsed: TypeId = typeid.parse().unwrap();
+
+            assert_eq!(parsed.prefix(), prefix, "{typeid}");
+            assert_eq!(parsed.uuid().to_string_hex(), uuid, "{typeid}");
+
+            let encoded = TypeId::from_uuid(prefix, Uuid::parse(uuid).unwrap()).unwrap();
+            assert_eq!(encoded.to_string(), typeid);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        // Test vectors from invalid.yml of the specification
+        let invalid = [
+            "PREFIX_00000000000000000000000000",
+            "12345_00000000000000000000000000",
+            "pre.fix_00000000000000000000000000",
+            "préfix_00000000000000000000000000",
+            "  prefix_00000000000000000000000000",
+            "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijkl_00000000000000000000000000",
+            "_00000000000000000000000000",
+            "_",
+            "prefix_1234567890123456789012345",
+            "prefix_123456789012345678901234567",
+            "prefix_1234567890123456789012345 ",
+            "prefix_0123456789ABCDEFGHJKMNPQRS",
+            "prefix_123456789-123456789-123456",
+            "prefix_ooooooiiiiiiuuuuuuulllllll",
+            "prefix_i23456789ol23456789oi23456",
+            "prefix_123456789-0123456789-0123456",
+            "prefix_8zzzzzzzzzzzzzzzzzzzzzzzzz",
+            "_prefix_00000000000000000000000000",
+            "prefix__00000000000000000000000000",
+            "",
+            "prefix_",
+        ];
+
+        for typeid in invalid {
+            assert!(typeid.parse::<TypeId>().is_err(), "{typeid}");
+        }
+
+        assert_eq!(
+            TypeId::from_uuid("User", crate::wellknown::Nil),
+            Err(TypeIdError::InvalidPrefix)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_new() {
+        let id = TypeId::new("user").unwrap();
+
+        assert_eq!(id.uuid().details().version, 7);
+        assert_eq!(id.to_string().parse(), Ok(id));
+    }
+}
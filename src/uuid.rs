@@ -1,41 +1,17 @@
+use alloc::string::{String, ToString};
 use core::fmt::{Debug, Display};
-use phf::phf_map;
 
 use crate::inspect::{UuidFields, UuidDetails};
 
-trait OctetHex<'a>
-where
-    Self: 'a + IntoIterator<Item = &'a u8> + Sized,
-{
-    fn output_hex(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        for oct in self.into_iter() {
-            write!(f, "{:02x}", oct)?;
-        }
-
-        Ok(())
-    }
-}
-
-impl<'a> OctetHex<'a> for &'a [u8] {}
-
 #[derive(Clone, PartialEq, Eq)]
-pub struct Uuid(u128);
+pub struct Uuid(pub(crate) u128);
 
 impl Display for Uuid {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let octets = self.0.to_be_bytes();
-
-        octets[0..=3].output_hex(f)?;
-        write!(f, "-")?;
-        octets[4..=5].output_hex(f)?;
-        write!(f, "-")?;
-        octets[6..=7].output_hex(f)?;
-        write!(f, "-")?;
-        octets[8..=9].output_hex(f)?;
-        write!(f, "-")?;
-        octets[10..=15].output_hex(f)?;
+        let output = crate::hex::encode_hyphenated(self.0);
 
-        Ok(())
+        // Hex digits and dashes are ASCII
+        f.write_str(core::str::from_utf8(&output).unwrap())
     }
 }
 
@@ -49,33 +25,6 @@ impl Debug for Uuid {
     }
 }
 
-static HEX_TO_INT_TBL: phf::Map<char, u8> = phf_map! {
-    '0' => 0,
-    '1' => 1,
-    '2' => 2,
-    '3' => 3,
-    '4' => 4,
-    '5' => 5,
-    '6' => 6,
-    '7' => 7,
-    '8' => 8,
-    '9' => 9,
-
-    'a' => 10,
-    'b' => 11,
-    'c' => 12,
-    'd' => 13,
-    'e' => 14,
-    'f' => 15,
-
-    'A' => 10,
-    'B' => 11,
-    'C' => 12,
-    'D' => 13,
-    'E' => 14,
-    'F' => 15,
-};
-
 pub(crate) type Octets = [u8; 16];
 
 impl Uuid {
@@ -84,32 +33,107 @@ impl Uuid {
     }
 
     #[inline(always)]
-    pub(crate) fn from_octets(mut octets: Octets, version_hi: u8) -> Self {
+    pub(crate) const fn from_octets(mut octets: Octets, version_hi: u8) -> Self {
         octets[6] = (octets[6] & 0x0f) | (version_hi << 4);
         octets[8] = (octets[8] & 0x3f) | 0x80;
 
         Uuid(u128::from_be_bytes(octets))
     }
 
+    /// Returns the 122 bits that are neither version nor variant bits, packed at the
+    /// bottom: the 48 bits before the version, the 12 bits between the version and the
+    /// variant, and the 62 bits after the variant
+    pub(crate) const fn payload(&self) -> u128 {
+        let value = self.0;
+        (value >> 80) << 74 | (value >> 64 & 0xfff) << 62 | value & ((1 << 62) - 1)
+    }
+
+    /// Builds a UUID of the RFC 9562 variant and the given version from 122 bits packed
+    /// as by [`Uuid::payload`]
+    #[cfg(feature = "cipher")]
+    pub(crate) const fn from_payload(payload: u128, version: u8) -> Self {
+        Uuid(
+            (payload >> 74) << 80
+                | (version as u128) << 76
+                | (payload >> 62 & 0xfff) << 64
+                | 0b10 << 62
+                | payload & ((1 << 62) - 1),
+        )
+    }
+
     pub fn to_string_hex(&self) -> String {
-        format!("{self}")
+        self.to_string()
     }
 
     pub fn to_string_hex_joined(&self) -> String {
-        let mut output = String::with_capacity(32);
+        let output = crate::hex::encode(self.0);
+
+        // Hex digits are ASCII
+        core::str::from_utf8(&output).unwrap().to_string()
+    }
 
-        for oct in self.0.to_be_bytes() {
-            let byte_str = format!("{:02x}", oct);
-            output.push_str(&byte_str);
+    /// Parses a UUID in a `const` context.
+    ///
+    /// Accepts the 8-4-4-4-12 format, the 32-length hex string format, and the latter
+    /// with a `0x` or `0X` prefix. Panics if `value` is not a valid UUID, which
+    /// becomes a compile error when evaluated at compile time. The [`uuid!`](crate::uuid!)
+    /// macro wraps this function and guarantees compile time evaluation.
+    pub const fn parse_const(value: &str) -> Self {
+        let bytes = value.as_bytes();
+
+        let mut i = 0;
+        if bytes.len() > 2 && bytes[0] == b'0' && (bytes[1] == b'x' || bytes[1] == b'X') {
+            i = 2;
         }
 
-        output
+        let dashed = match bytes.len() - i {
+            36 if i == 0 => true,
+            32 => false,
+            _ => panic!("invalid UUID length"),
+        };
+
+        let mut intval = 0u128;
+        let mut pos = 0;
+
+        while i < bytes.len() {
+            let ch = bytes[i];
+
+            if dashed && matches!(pos, 8 | 13 | 18 | 23) {
+                if ch != b'-' {
+                    panic!("expected '-' in UUID");
+                }
+            } else {
+                let as_int = match ch {
+                    b'0'..=b'9' => ch - b'0',
+                    b'a'..=b'f' => ch - b'a' + 10,
+                    b'A'..=b'F' => ch - b'A' + 10,
+                    _ => panic!("invalid hex digit in UUID"),
+                };
+
+                intval = intval << 4 | as_int as u128;
+            }
+
+            i += 1;
+            pos += 1;
+        }
+
+        Uuid(intval)
     }
 
-    pub fn value(&self) -> u128 {
+    pub const fn value(&self) -> u128 {
         self.0
     }
 
+    /// Tells whether this is the Nil UUID, see [`wellknown::Nil`]
+    pub const fn is_nil(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Tells whether this is the Max UUID, see [`wellknown::MAX`]
+    pub const fn is_max(&self) -> bool {
+        self.0 == u128::MAX
+    }
+
     #[inline]
     pub fn fields(&self) -> UuidFields {
         UuidFields::of(self)
@@ -117,9 +141,42 @@ impl Uuid {
 
     #[inline]
     pub fn details(&self) -> UuidDetails {
-        UuidDetails::construct(&self.fields())
+        UuidDetails::of(self, &self.fields())
+    }
+
+    /// Returns the octets in the order produced by MySQL's `UUID_TO_BIN(uuid, 1)`, which
+    /// moves `time_hi_and_version` and `time_mid` before `time_low` so that V1 UUIDs
+    /// stored in `BINARY(16)` columns are ordered by time
+    ///
+    /// ```
+    /// let uuid = uuidland::uuid!("6ccd780c-baba-1026-9564-5b8c656024db");
+    ///
+    /// assert_eq!(uuid.to_mysql_swapped_bytes(), 0x1026baba6ccd780c95645b8c656024db_u128.to_be_bytes());
+    /// ```
+    pub fn to_mysql_swapped_bytes(&self) -> [u8; 16] {
+        let fields = self.fields();
+        let mut octets = self.value().to_be_bytes();
+
+        octets[0..2].copy_from_slice(&fields.time_hi_and_version.to_be_bytes());
+        octets[2..4].copy_from_slice(&fields.time_mid.to_be_bytes());
+        octets[4..8].copy_from_slice(&fields.time_low.to_be_bytes());
+
+        octets
     }
 
+    /// Reads octets in the order produced by MySQL's `UUID_TO_BIN(uuid, 1)`, the inverse
+    /// of [`Uuid::to_mysql_swapped_bytes`]. This is what `BIN_TO_UUID(bin, 1)` does
+    pub fn from_mysql_swapped_bytes(octets: [u8; 16]) -> Self {
+        let mut ordered = octets;
+
+        ordered[0..4].copy_from_slice(&octets[4..8]);
+        ordered[4..6].copy_from_slice(&octets[2..4]);
+        ordered[6..8].copy_from_slice(&octets[0..2]);
+
+        Self::from_value(u128::from_be_bytes(ordered))
+    }
+
+    #[allow(clippy::result_unit_err)]
     pub fn parse<T: AsRef<str>>(value: T) -> Result<Self, ()> {
         // Parses the following formats:
         //      8-4-4-4-12 format:
@@ -132,43 +189,58 @@ impl Uuid {
         //          0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
         //          0Xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
 
-        let mut value = value.as_ref();
-
-        if value.starts_with("0x") {
-            value = value.strip_prefix("0x").unwrap();
-        } else if value.starts_with("0X") {
-            value = value.strip_prefix("0X").unwrap();
-        }
-
-        let mut intval = 0u128;
-        let mut consumed = 0;
-
-        for (i, s) in value.chars().enumerate() {
-
-            let as_int: u128 = match (i, s) {
-                // Dashes are only allowed at these indices
-                (8 | 13 | 18 | 23, '-') => continue,
+        Self::parse_ascii(value.as_ref().as_bytes())
+    }
 
-                // Other characters
-                (_, s) => match HEX_TO_INT_TBL.get(&s) {
-                    Some(&val) => val as u128,
-                    None => return Err(())
+    /// Parses a UUID from ASCII bytes, accepting the same formats as [`Uuid::parse`].
+    /// The whole input must be a UUID
+    #[allow(clippy::result_unit_err)]
+    pub fn parse_ascii(value: &[u8]) -> Result<Self, ()> {
+        // The length tells the format apart
+        let digits: [u8; 32] = match value.len() {
+            36 => {
+                // Dashes are required at these indices, and only allowed there
+                if [8, 13, 18, 23].iter().any(|&i| value[i] != b'-') {
+                    return Err(());
                 }
-            };
-
-            intval = intval << 4 | as_int;
-            consumed += 1;
 
-            if consumed == 32 {
-                break;
+                let mut digits = [0; 32];
+                digits[0..8].copy_from_slice(&value[0..8]);
+                digits[8..12].copy_from_slice(&value[9..13]);
+                digits[12..16].copy_from_slice(&value[14..18]);
+                digits[16..20].copy_from_slice(&value[19..23]);
+                digits[20..32].copy_from_slice(&value[24..36]);
+                digits
             }
-        }
+            32 => value.try_into().unwrap(),
+            34 if value.starts_with(b"0x") || value.starts_with(b"0X") => value[2..].try_into().unwrap(),
+            _ => return Err(()),
+        };
 
-        if consumed == 32 {
-            Ok(Uuid(intval))
-        } else {
-            Err(())
-        }
+        crate::hex::decode(&digits).map(Uuid).ok_or(())
+    }
+
+    /// Parses the longest UUID at the start of `input`, in any of the formats accepted
+    /// by [`Uuid::parse`], and returns it along with the number of bytes it spans. The
+    /// rest of the input is ignored
+    ///
+    /// ```
+    /// use uuidland::Uuid;
+    ///
+    /// let input = b"6ba7b810-9dad-11d1-80b4-00c04fd430c8 GET /index.html";
+    /// let (uuid, consumed) = Uuid::try_parse_prefix(input).unwrap();
+    ///
+    /// assert_eq!(uuid, uuidland::wellknown::NS_DNS);
+    /// assert_eq!(&input[consumed..], b" GET /index.html");
+    /// ```
+    #[allow(clippy::result_unit_err)]
+    pub fn try_parse_prefix(input: &[u8]) -> Result<(Self, usize), ()> {
+        // The 8-4-4-4-12 format, the 0x prefixed format, then the 32-length format
+        [36, 34, 32]
+            .into_iter()
+            .filter_map(|len| input.get(..len))
+            .find_map(|prefix| Some((Self::parse_ascii(prefix).ok()?, prefix.len())))
+            .ok_or(())
     }
 }
 
@@ -192,19 +264,25 @@ impl TryFrom<String> for Uuid {
 pub mod wellknown {
     use super::Uuid;
 
+    /// The Nil UUID, with all bits set to zero (RFC 9562 Section 5.9)
     pub const Nil: Uuid = Uuid::from_value(0);
 
-    pub const NS_DNS: Uuid = Uuid::from_value(143098242404177361603877621312831893704);
-    pub const NS_URL: Uuid = Uuid::from_value(143098242483405524118141958906375844040);
-    pub const NS_OID: Uuid = Uuid::from_value(143098242562633686632406296499919794376);
-    pub const NS_X500: Uuid = Uuid::from_value(143098242721090011660934971687007695048);
+    /// The Max UUID, with all bits set to one (RFC 9562 Section 5.10)
+    pub const MAX: Uuid = Uuid::from_value(u128::MAX);
+
+    pub const NS_DNS: Uuid = crate::uuid!("6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+    pub const NS_URL: Uuid = crate::uuid!("6ba7b811-9dad-11d1-80b4-00c04fd430c8");
+    pub const NS_OID: Uuid = crate::uuid!("6ba7b812-9dad-11d1-80b4-00c04fd430c8");
+    pub const NS_X500: Uuid = crate::uuid!("6ba7b814-9dad-11d1-80b4-00c04fd430c8");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::format;
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_version_bits() {
         let extver = move |uuid: Uuid| (uuid.0.to_be_bytes()[6] & 0xf0) >> 4;
 
@@ -242,6 +320,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_parse() {
         let uuid = crate::gen::v4();
 
@@ -249,4 +328,110 @@ mod tests {
         assert_eq!(uuid, uuid.to_string_hex_joined().try_into().unwrap());
         assert_eq!(uuid, Uuid::from_value(uuid.value()));
     }
+
+    #[test]
+    fn test_parse_ascii() {
+        assert_eq!(Uuid::parse_ascii(b"6ba7b810-9dad-11d1-80b4-00c04fd430c8"), Ok(wellknown::NS_DNS));
+        assert_eq!(Uuid::parse_ascii(b"6BA7B8109DAD11D180B400C04FD430C8"), Ok(wellknown::NS_DNS));
+        assert_eq!(Uuid::parse_ascii(b"0x6ba7b8109dad11d180b400c04fd430c8"), Ok(wellknown::NS_DNS));
+        assert_eq!(Uuid::parse_ascii(b"6ba7b810-9dad-11d1-80b4-00c04fd430c8\0"), Err(()));
+        assert_eq!(Uuid::parse_ascii(b"6ba7b810-9dad-11d1-80b4-00c04fd430\xc3\xa9"), Err(()));
+    }
+
+    #[test]
+    fn test_try_parse_prefix() {
+        let cases: [(&[u8], usize); 5] = [
+            (b"6ba7b810-9dad-11d1-80b4-00c04fd430c8", 36),
+            (b"6ba7b810-9dad-11d1-80b4-00c04fd430c8-ffff", 36),
+            (b"0x6ba7b8109dad11d180b400c04fd430c8ffff", 34),
+            (b"6ba7b8109dad11d180b400c04fd430c8-9dad-11d1", 32),
+            (b"6ba7b8109dad11d180b400c04fd430c8 6ba7b810", 32),
+        ];
+
+        for (input, consumed) in cases {
+            assert_eq!(Uuid::try_parse_prefix(input), Ok((wellknown::NS_DNS, consumed)));
+        }
+
+        assert_eq!(Uuid::try_parse_prefix(b""), Err(()));
+        assert_eq!(Uuid::try_parse_prefix(b"6ba7b810-9dad-11d1-80b4-00c04fd430c"), Err(()));
+        assert_eq!(Uuid::try_parse_prefix(b" 6ba7b810-9dad-11d1-80b4-00c04fd430c8"), Err(()));
+        assert_eq!(Uuid::try_parse_prefix(b"0x6ba7b810-9dad-11d1-80b4-00c04fd430c8"), Err(()));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        let valid = "ffb82219-2be8-4961-8c83-2163e1b4b966";
+        assert!(Uuid::parse(valid).is_ok());
+
+        let invalid = [
+            // Trailing or missing input
+            "ffb82219-2be8-4961-8c83-2163e1b4b966a",
+            "ffb82219-2be8-4961-8c83-2163e1b4b966-",
+            "ffb822192be849618c832163e1b4b966ffff",
+            "ffb822192be849618c832163e1b4b96",
+            "0xffb822192be849618c832163e1b4b966ff",
+            "",
+            // Dashes missing, misplaced or combined with the 0x prefix
+            "ffb82219-2be849618c832163e1b4b966",
+            "ffb822192-be8-4961-8c83-2163e1b4b966",
+            "ffb82219-2be8-4961-8c832163e1b4b9660",
+            "0xffb82219-2be8-4961-8c83-2163e1b4b966",
+            "0x-ffb822192be849618c832163e1b4b966",
+            // Non-hex or non-ASCII characters
+            "ffb82219-2be8-4961-8c83-2163e1b4b96g",
+            "ffb82219-2be8-4961-8c83-2163e1b4b9é",
+            "0Zffb822192be849618c832163e1b4b966",
+        ];
+
+        for value in invalid {
+            assert_eq!(Uuid::parse(value), Err(()), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_parse_const() {
+        let uuid = Uuid::parse("ffb82219-2be8-4961-8c83-2163e1b4b966").unwrap();
+
+        assert_eq!(uuid, Uuid::parse_const("ffb82219-2be8-4961-8c83-2163e1b4b966"));
+        assert_eq!(uuid, Uuid::parse_const("FFB822192BE849618C832163E1B4B966"));
+        assert_eq!(uuid, Uuid::parse_const("0xffb822192be849618c832163e1b4b966"));
+        assert_eq!(uuid, crate::uuid!("ffb82219-2be8-4961-8c83-2163e1b4b966"));
+
+        assert_eq!(wellknown::NS_DNS.value(), 143098242404177361603877621312831893704);
+        assert_eq!(wellknown::NS_X500.value(), 143098242721090011660934971687007695048);
+    }
+
+    #[test]
+    fn test_mysql_swapped_bytes() {
+        // Examples from the MySQL documentation of UUID_TO_BIN() and BIN_TO_UUID()
+        let uuid = crate::uuid!("6ccd780c-baba-1026-9564-5b8c656024db");
+        let swapped = 0x1026BABA6CCD780C95645B8C656024DB_u128.to_be_bytes();
+
+        assert_eq!(uuid.to_mysql_swapped_bytes(), swapped);
+        assert_eq!(Uuid::from_mysql_swapped_bytes(swapped), uuid);
+
+        // Without the swap flag, the octets are in the usual order
+        assert_eq!(uuid.value().to_be_bytes(), 0x6CCD780CBABA102695645B8C656024DB_u128.to_be_bytes());
+
+        for uuid in [wellknown::NS_DNS, wellknown::Nil, wellknown::MAX] {
+            assert_eq!(Uuid::from_mysql_swapped_bytes(uuid.to_mysql_swapped_bytes()), uuid);
+        }
+    }
+
+    #[test]
+    fn test_nil_max() {
+        assert!(wellknown::Nil.is_nil());
+        assert!(!wellknown::Nil.is_max());
+        assert!(wellknown::MAX.is_max());
+        assert!(!wellknown::MAX.is_nil());
+        assert!(!wellknown::NS_DNS.is_nil() && !wellknown::NS_DNS.is_max());
+
+        assert_eq!(wellknown::MAX.to_string_hex(), "ffffffff-ffff-ffff-ffff-ffffffffffff");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parse_const_invalid() {
+        Uuid::parse_const("ffb82219-2be8-4961-8c83_2163e1b4b966");
+    }
 }
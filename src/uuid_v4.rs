@@ -2,9 +2,14 @@ use crate::uuid::{Octets, Uuid};
 
 use rand::RngCore;
 
+#[cfg(feature = "std")]
 pub fn v4() -> Uuid {
+    v4_with_rng(&mut rand::thread_rng())
+}
+
+/// Generates a V4 UUID using the given random number generator
+pub fn v4_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Uuid {
     let mut octets = Octets::default();
-    let mut rng = rand::thread_rng();
     rng.fill_bytes(&mut octets);
 
     Uuid::from_octets(octets, 0x4)
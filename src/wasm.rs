@@ -0,0 +1,91 @@
+//! JavaScript API, exported with `wasm-bindgen` when the `wasm-bindgen` feature is
+//! enabled.
+//!
+//! UUIDs cross the boundary as strings in the 8-4-4-4-12 format, or as a
+//! `Uint8Array` of 16 octets. Invalid input is reported by throwing an `Error`.
+//! The TypeScript declarations generated by `wasm-bindgen` are checked in as
+//! `js/uuidland.d.ts`, and CI fails if they no longer match the generated ones.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::inspect::UuidDetails;
+use crate::uuid::Uuid;
+
+fn parse_uuid(uuid: &str) -> Result<Uuid, JsError> {
+    Uuid::parse(uuid).map_err(|_| JsError::new(&alloc::format!("Invalid UUID: {uuid:?}")))
+}
+
+fn parse_namespace(namespace: Option<String>) -> Result<Option<Uuid>, JsError> {
+    namespace.as_deref().map(parse_uuid).transpose()
+}
+
+/// Generates a time based (V1) UUID
+#[wasm_bindgen]
+pub fn v1() -> Result<String, JsError> {
+    Ok(crate::gen::v1()?.to_string_hex())
+}
+
+/// Generates a random (V4) UUID
+#[wasm_bindgen]
+pub fn v4() -> String {
+    crate::gen::v4().to_string_hex()
+}
+
+/// Generates a name based (V5) UUID from a string name. A random namespace is used
+/// if none is given
+#[wasm_bindgen]
+pub fn v5(name: &str, namespace: Option<String>) -> Result<String, JsError> {
+    v5_from_bytes(name.as_bytes(), namespace)
+}
+
+/// Generates a name based (V5) UUID from a binary name. A random namespace is used
+/// if none is given
+#[wasm_bindgen(js_name = v5FromBytes)]
+pub fn v5_from_bytes(name: &[u8], namespace: Option<String>) -> Result<String, JsError> {
+    let namespace = parse_namespace(namespace)?;
+    Ok(crate::gen::v5(name, namespace).to_string_hex())
+}
+
+/// Generates a Unix Epoch time based (V7) UUID
+#[wasm_bindgen]
+pub fn v7() -> Result<String, JsError> {
+    Ok(crate::gen::v7()?.to_string_hex())
+}
+
+/// Parses a UUID in any of the supported formats and returns it in the 8-4-4-4-12
+/// format
+#[wasm_bindgen]
+pub fn parse(uuid: &str) -> Result<String, JsError> {
+    Ok(parse_uuid(uuid)?.to_string_hex())
+}
+
+/// Returns the decoded fields of a UUID
+#[wasm_bindgen]
+pub fn inspect(uuid: &str) -> Result<UuidDetails, JsError> {
+    Ok(parse_uuid(uuid)?.details())
+}
+
+/// Formats a UUID as a 32-length hex string without dashes
+#[wasm_bindgen(js_name = toHexJoined)]
+pub fn to_hex_joined(uuid: &str) -> Result<String, JsError> {
+    Ok(parse_uuid(uuid)?.to_string_hex_joined())
+}
+
+/// Returns the 16 octets of a UUID, most significant first
+#[wasm_bindgen(js_name = toBytes)]
+pub fn to_bytes(uuid: &str) -> Result<Vec<u8>, JsError> {
+    Ok(parse_uuid(uuid)?.value().to_be_bytes().to_vec())
+}
+
+/// Builds a UUID from 16 octets, most significant first
+#[wasm_bindgen(js_name = fromBytes)]
+pub fn from_bytes(bytes: &[u8]) -> Result<String, JsError> {
+    let octets: [u8; 16] = bytes
+        .try_into()
+        .map_err(|_| JsError::new("A UUID must be exactly 16 bytes long"))?;
+
+    Ok(Uuid::from_value(u128::from_be_bytes(octets)).to_string_hex())
+}
@@ -0,0 +1,83 @@
+//! Property tests for parsing and formatting UUIDs in every supported format
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use proptest::prelude::*;
+use uuidland::encoding::ShortCodec;
+use uuidland::typeid::TypeId;
+use uuidland::Uuid;
+
+fn any_uuid() -> impl Strategy<Value = Uuid> {
+    any::<u128>().prop_map(Uuid::from_value)
+}
+
+proptest! {
+    #[test]
+    fn hex_round_trip(uuid in any_uuid()) {
+        let hyphenated = uuid.to_string_hex();
+        let simple = uuid.to_string_hex_joined();
+
+        prop_assert_eq!(Uuid::parse(&hyphenated), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::parse(hyphenated.to_ascii_uppercase()), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::parse(&simple), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::parse(simple.to_ascii_uppercase()), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::parse(format!("0x{simple}")), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::parse(format!("0X{simple}")), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::parse_const(&hyphenated), uuid.clone());
+        prop_assert_eq!(Uuid::parse_const(&simple), uuid);
+    }
+
+    #[test]
+    fn encoding_round_trip(uuid in any_uuid()) {
+        prop_assert_eq!(Uuid::from_base32(&uuid.to_base32()), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::from_ulid_str(&uuid.to_ulid_string()), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::from_base58(&uuid.to_base58()), Ok(uuid.clone()));
+        prop_assert_eq!(Uuid::from_base64url(&uuid.to_base64url()), Ok(uuid.clone()));
+
+        let codec = ShortCodec::default();
+        prop_assert_eq!(codec.decode(&codec.encode(&uuid)), Ok(uuid.clone()));
+
+        let typeid = TypeId::from_uuid("user", uuid.clone()).unwrap();
+        prop_assert_eq!(typeid.to_string().parse::<TypeId>().map(|id| id.uuid().clone()), Ok(uuid));
+    }
+
+    #[test]
+    fn parse_accepts_only_canonical_forms(value in "(0[xX])?[0-9a-fA-F-]{28,38}") {
+        // Anything accepted must be one of the formats produced by the formatting code
+        if let Ok(uuid) = Uuid::parse(&value) {
+            let lower = value.to_ascii_lowercase();
+            let simple = uuid.to_string_hex_joined();
+
+            prop_assert!(
+                lower == uuid.to_string_hex() || lower == simple || lower == format!("0x{simple}"),
+                "{} parsed as {}", value, uuid
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_trailing_input(uuid in any_uuid(), suffix in ".+") {
+        prop_assert_eq!(Uuid::parse(format!("{}{suffix}", uuid.to_string_hex())), Err(()));
+        prop_assert_eq!(Uuid::parse(format!("{}{suffix}", uuid.to_string_hex_joined())), Err(()));
+    }
+
+    #[test]
+    fn parse_prefix_ignores_trailing_input(uuid in any_uuid(), suffix in any::<Vec<u8>>()) {
+        let mut input = uuid.to_string_hex().into_bytes();
+        input.extend_from_slice(&suffix);
+
+        prop_assert_eq!(Uuid::try_parse_prefix(&input), Ok((uuid, 36)));
+    }
+
+    #[test]
+    fn parse_never_panics(value in any::<String>()) {
+        let _ = Uuid::parse(&value);
+        let _ = Uuid::try_parse_prefix(value.as_bytes());
+        let _ = Uuid::from_base32(&value);
+        let _ = Uuid::from_ulid_str(&value);
+        let _ = Uuid::from_base58(&value);
+        let _ = Uuid::from_base64url(&value);
+        let _ = ShortCodec::default().decode(&value);
+        let _ = value.parse::<TypeId>();
+    }
+}
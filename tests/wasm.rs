@@ -0,0 +1,51 @@
+//! Tests for the JavaScript API. Run under Node with
+//! `CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner cargo test
+//! --target wasm32-unknown-unknown --features wasm-bindgen --test wasm`
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
+
+use uuidland::wasm;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn test_generate() {
+    assert_eq!(wasm::parse(&wasm::v1().unwrap()).unwrap().as_bytes()[14], b'1');
+    assert_eq!(wasm::v4().as_bytes()[14], b'4');
+    assert_eq!(wasm::v7().unwrap().as_bytes()[14], b'7');
+}
+
+#[wasm_bindgen_test]
+fn test_v5() {
+    let ns_dns = Some("6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string());
+
+    assert_eq!(
+        wasm::v5("foobar", ns_dns.clone()).unwrap(),
+        "a050b517-6677-5119-9a77-2d26bbf30507"
+    );
+    assert_eq!(
+        wasm::v5_from_bytes(b"foobar", ns_dns).unwrap(),
+        "a050b517-6677-5119-9a77-2d26bbf30507"
+    );
+    assert!(wasm::v5("foobar", Some("not a uuid".to_string())).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_parse_and_format() {
+    let uuid = "ffb82219-2be8-4961-8c83-2163e1b4b966";
+
+    assert_eq!(wasm::parse("FFB822192BE849618C832163E1B4B966").unwrap(), uuid);
+    assert_eq!(wasm::to_hex_joined(uuid).unwrap(), "ffb822192be849618c832163e1b4b966");
+    assert_eq!(wasm::from_bytes(&wasm::to_bytes(uuid).unwrap()).unwrap(), uuid);
+
+    assert!(wasm::parse("ffb82219").is_err());
+    assert!(wasm::from_bytes(&[0; 15]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_inspect() {
+    let details = wasm::inspect("e47e7da8-adf1-11ee-b053-325096b39f47").unwrap();
+
+    assert_eq!(details.version, 1);
+    assert_eq!(details.clock_seq, 12371);
+    assert_eq!(details.node, 0x32_50_96_b3_9f_47);
+}